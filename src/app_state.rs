@@ -1,46 +1,239 @@
-use crate::response::{Response, parse_response_content};
+use crate::cookies::{CookieJar, DEFAULT_ENVIRONMENT, EnvironmentCookies, StoredCookie};
+use crate::decode::{self, BinaryFormat};
+use crate::diff::Comparison;
+use crate::download::{BodySink, DEFAULT_MAX_IN_MEMORY_BYTES, DEFAULT_PREVIEW_BYTES, DownloadProgress};
+use crate::grpc::{self, CallEvent, CallSpec, GrpcCall, GrpcSchema};
+use crate::json_filter::FilterStore;
+use crate::ndjson::{self, NdJsonParser, NdJsonRecord};
+use crate::response::{
+    RedirectHop, Response, ResponseBody, ResponseContent, collect_headers, collect_set_cookies,
+    decoded_content, parse_response_content,
+};
+use crate::snapshots::{Snapshot, SnapshotStore};
+use crate::sse::{SseEvent, SseParser};
+use crate::timing::{self, ConnectTimingLayer, TimedResolver, Timings, TlsDetails};
+use crate::websocket::{
+    self, Handshake, LogEntry, MessageFormat, SessionEvent, SessionStatus, WebSocketSession,
+};
+use encoding_rs::Encoding;
+use futures::StreamExt;
+use futures::channel::mpsc::{UnboundedSender, unbounded};
 use gpui::*;
-use std::sync::OnceLock;
-use std::time::Instant;
+use reqwest::header::{AUTHORIZATION, CONTENT_LENGTH, COOKIE, LOCATION};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
 pub static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
 
+/// Upper bound on followed redirects before the request is reported as failed.
+const MAX_REDIRECTS: usize = 10;
+/// Minimum interval between two download progress updates sent to the UI.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+/// Tail of a body of unknown length that is rendered while it downloads.
+const LIVE_TAIL_BYTES: usize = 64 * 1024;
+/// Reconnection delay for event streams until the server sends a `retry:` field.
+const DEFAULT_SSE_RETRY: Duration = Duration::from_millis(3000);
+/// Failed reconnection attempts in a row after which an event stream gives up.
+const MAX_SSE_RECONNECT_FAILURES: u32 = 5;
+/// Longest delay between reconnection attempts once failures back off.
+const MAX_SSE_RETRY: Duration = Duration::from_secs(60);
+/// Finished responses kept in memory for comparing.
+const RESPONSE_HISTORY_LIMIT: usize = 20;
+
+/// A finished response kept for comparing against later ones.
+#[derive(Clone)]
+pub struct ResponseEntry {
+    pub id: u64,
+    /// The request that produced it, as `METHOD url`.
+    pub request: SharedString,
+    pub response: Arc<Response>,
+    /// Set when the response is a pinned snapshot rather than a run.
+    pub snapshot: Option<Snapshot>,
+}
+
+/// How the last response compares to the newest snapshot of its request.
+#[derive(Clone)]
+pub struct SnapshotCheck {
+    /// `ResponseEntry::id` of the snapshot.
+    pub entry_id: u64,
+    pub name: SharedString,
+    /// The status or body changed; headers are not considered.
+    pub differs: bool,
+}
+
+/// Everything needed to send one request, detached from the UI state.
+struct RequestSpec {
+    method: SharedString,
+    url: SharedString,
+    queries: Vec<(SharedString, SharedString)>,
+    headers: Vec<(SharedString, SharedString)>,
+    body: SharedString,
+    max_in_memory_bytes: usize,
+    preview_bytes: usize,
+    sse_auto_reconnect: bool,
+}
+
+/// Updates sent from the request task on the Tokio runtime back to the UI.
+enum RequestEvent {
+    Progress(DownloadProgress),
+    /// Headers of an event stream or NDJSON body arrived; its content
+    /// follows as it is received.
+    Streaming(Box<Response>),
+    Event(SseEvent),
+    Records(Vec<NdJsonRecord>),
+    /// Latest tail of a text body of unknown length, still downloading.
+    Partial(SharedString),
+    Finished(Box<Response>),
+}
+
 pub struct AppState {
     pub url: SharedString,
     pub method: SharedString,
     pub history: Vec<SharedString>,
-    pub response: Option<Response>,
+    /// Recent finished responses, oldest first.
+    pub responses: VecDeque<ResponseEntry>,
+    /// Snapshots pinned to the request of the last finished response.
+    pub pinned: Vec<ResponseEntry>,
+    pub snapshot_check: Option<SnapshotCheck>,
+    /// Why pinning or loading a snapshot failed.
+    pub snapshot_error: Option<SharedString>,
+    snapshots: SnapshotStore,
+    pub response: Option<Arc<Response>>,
+    /// The request that produced `response`, as `METHOD url`.
+    response_request: SharedString,
+    /// Progress of the body download while a request is in flight.
+    pub download: Option<DownloadProgress>,
+    /// Text received so far for chunked responses without `Content-Length`.
+    pub partial_body: Option<SharedString>,
+    /// Response bodies larger than this are spooled to a temporary file.
+    pub max_in_memory_bytes: usize,
+    /// Leading part of a spooled body kept in memory for display.
+    pub preview_bytes: usize,
+    /// Reconnect event streams with `Last-Event-ID` when the server closes them.
+    pub sse_auto_reconnect: bool,
+    /// Charset forced on the response on screen instead of the detected one.
+    pub encoding_override: Option<&'static Encoding>,
     pub body: SharedString,
     pub headers: Vec<(SharedString, SharedString)>,
     pub queries: Vec<(SharedString, SharedString)>,
+    pub environment: SharedString,
+    /// Last JSON filter expression per request, keyed by `request_key`.
+    json_filters: FilterStore,
+    /// Live or last WebSocket connection, replaced on every connect.
+    pub websocket: Option<WebSocketSession>,
+    /// Comma separated `Sec-WebSocket-Protocol` values offered on connect.
+    pub subprotocols: SharedString,
+    pub message_format: MessageFormat,
+    /// Services loaded from `.proto` files or server reflection.
+    pub grpc_schema: Option<Arc<GrpcSchema>>,
+    /// Progress or error of the last schema load.
+    pub grpc_schema_status: Option<SharedString>,
+    /// Path of the selected method, e.g. `/helloworld.Greeter/SayHello`.
+    pub grpc_method: Option<SharedString>,
+    pub grpc_call: Option<GrpcCall>,
+    /// Message type used to decode protobuf response bodies, raw wire format when unset.
+    pub protobuf_message: Option<SharedString>,
+    /// Why the body did not decode as `protobuf_message`.
+    pub protobuf_error: Option<SharedString>,
+    cookie_jar: Arc<CookieJar>,
     client: reqwest::Client,
+    /// Id, `request_key` and task of the request in flight.
+    pending: Option<(u64, SharedString, tokio::task::AbortHandle)>,
+    next_request_id: u64,
 }
 
 impl AppState {
     pub fn new() -> Self {
+        let cookie_jar = Arc::new(CookieJar::load());
         Self {
             url: "https://api.github.com".into(),
             method: "GET".into(),
             history: vec![],
+            responses: VecDeque::new(),
+            pinned: vec![],
+            snapshot_check: None,
+            snapshot_error: None,
+            snapshots: SnapshotStore::load(),
             response: None,
+            response_request: SharedString::default(),
+            download: None,
+            partial_body: None,
+            max_in_memory_bytes: DEFAULT_MAX_IN_MEMORY_BYTES,
+            preview_bytes: DEFAULT_PREVIEW_BYTES,
+            sse_auto_reconnect: true,
+            encoding_override: None,
             body: "".into(),
             headers: vec![],
             queries: vec![],
-            client: reqwest::Client::builder()
-                .user_agent("gpui-app")
-                .build()
+            environment: DEFAULT_ENVIRONMENT.into(),
+            json_filters: FilterStore::load(),
+            websocket: None,
+            subprotocols: "".into(),
+            message_format: MessageFormat::Text,
+            grpc_schema: None,
+            grpc_schema_status: None,
+            grpc_method: None,
+            grpc_call: None,
+            protobuf_message: None,
+            protobuf_error: None,
+            cookie_jar: cookie_jar.clone(),
+            client: build_client(cookie_jar.environment(DEFAULT_ENVIRONMENT), None)
                 .expect("Failed to create reqwest client"),
+            pending: None,
+            next_request_id: 0,
         }
     }
 
+    /// WebSocket mode is chosen with the `WS` method or a `ws://`/`wss://` URL.
+    pub fn is_websocket(&self) -> bool {
+        self.method == "WS" || self.url.starts_with("ws://") || self.url.starts_with("wss://")
+    }
+
+    pub fn is_grpc(&self) -> bool {
+        self.method == "GRPC"
+    }
+
     pub fn send_request(&mut self, cx: &mut Context<Self>) {
+        if self.is_grpc() {
+            self.start_grpc_call(cx);
+            return;
+        }
+        if self.is_websocket() {
+            self.connect_websocket(cx);
+            return;
+        }
+        self.cancel_pending();
         self.response = None;
-        let url = self.url.clone();
-        let method = self.method.clone();
-        let body = self.body.clone();
-        let headers = self.headers.clone();
-        let queries = self.queries.clone();
-        let client = self.client.clone();
+        self.response_request = self.request_key();
+        self.encoding_override = None;
+        self.download = None;
+        self.partial_body = None;
+
+        // `unix://` URLs get a client bound to the socket; the rest share one client
+        let (client, url) = match split_unix_url(&self.url) {
+            Some((socket, url)) => match build_client(self.cookie_jar.environment(&self.environment), Some(socket)) {
+                Ok(client) => (client, url.into()),
+                Err(e) => {
+                    self.response = Some(Arc::new(Response::from_error(e)));
+                    cx.notify();
+                    return;
+                }
+            },
+            None => (self.client.clone(), self.url.clone()),
+        };
+
+        let spec = RequestSpec {
+            method: self.method.clone(),
+            url,
+            queries: self.queries.clone(),
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+            max_in_memory_bytes: self.max_in_memory_bytes,
+            preview_bytes: self.preview_bytes,
+            sse_auto_reconnect: self.sse_auto_reconnect,
+        };
 
         let handle = RUNTIME
             .get()
@@ -48,102 +241,547 @@ impl AppState {
             .handle()
             .clone();
 
-        cx.spawn(|model: WeakEntity<AppState>, cx: &mut AsyncApp| {
+        // Network and disk IO run on the Tokio runtime; the UI only applies events
+        let (events, mut receiver) = unbounded();
+        let task = handle.spawn(async move {
+            let response = execute_request(client, spec, &events).await;
+            let _ = events.unbounded_send(RequestEvent::Finished(Box::new(response)));
+        });
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+        self.pending = Some((request_id, self.request_key(), task.abort_handle()));
+
+        cx.spawn(move |model: WeakEntity<AppState>, cx: &mut AsyncApp| {
             let cx = cx.clone();
             async move {
-                let _guard = handle.enter();
-                let start = Instant::now();
-
-                let http_method = match method.as_ref() {
-                    "POST" => reqwest::Method::POST,
-                    "PUT" => reqwest::Method::PUT,
-                    "DELETE" => reqwest::Method::DELETE,
-                    "PATCH" => reqwest::Method::PATCH,
-                    _ => reqwest::Method::GET,
-                };
-
-                let mut url = reqwest::Url::parse(url.as_ref()).map_err(|e| Response::from_error(format!("Invalid URL: {}", e))).unwrap();
-                if !queries.is_empty() {
-                    let mut query_pairs = url.query_pairs_mut();
-                    for (k, v) in queries {
-                        query_pairs.append_pair(k.as_ref(), v.as_ref());
+                while let Some(event) = receiver.next().await {
+                    let _ = cx.update(|cx| {
+                        model.update(cx, |model, cx| model.apply_event(request_id, event, cx))
+                    });
+                }
+            }
+        })
+        .detach();
+
+        self.history
+            .push(format!("{} {}", self.method, self.url).into());
+        cx.notify();
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    pub fn cancel_request(&mut self, cx: &mut Context<Self>) {
+        if self.cancel_pending() {
+            self.download = None;
+            // A stopped event stream keeps the events received so far
+            if self.response.is_none() {
+                self.response = Some(Arc::new(Response::from_error("Request cancelled.".to_string())));
+            }
+            cx.notify();
+        }
+    }
+
+    fn cancel_pending(&mut self) -> bool {
+        match self.pending.take() {
+            Some((_, _, task)) => {
+                task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn apply_event(&mut self, request_id: u64, event: RequestEvent, cx: &mut Context<Self>) {
+        // Ignore events of requests that were cancelled or replaced
+        if self.pending.as_ref().map(|(id, _, _)| *id) != Some(request_id) {
+            return;
+        }
+        match event {
+            RequestEvent::Progress(progress) => {
+                // A streaming response is already shown; keep its size current
+                if let Some(response) = &mut self.response {
+                    Arc::make_mut(response).size_bytes = progress.received as usize;
+                }
+                self.download = Some(progress);
+            }
+            RequestEvent::Streaming(response) => {
+                self.download = None;
+                self.response = Some(Arc::new(*response));
+            }
+            RequestEvent::Event(event) => {
+                if let Some(response) = &mut self.response {
+                    let response = Arc::make_mut(response);
+                    response.size_bytes += event.data.len();
+                    if let ResponseContent::EventStream(events) = &mut response.content {
+                        events.push(event);
+                    }
+                }
+            }
+            RequestEvent::Records(records) => {
+                if let Some(response) = &mut self.response
+                    && let ResponseContent::NdJson(existing) = &mut Arc::make_mut(response).content
+                {
+                    existing.extend(records);
+                }
+            }
+            RequestEvent::Partial(text) => self.partial_body = Some(text),
+            RequestEvent::Finished(response) => {
+                let request = self.pending.take().map(|(_, key, _)| key).unwrap_or_default();
+                self.download = None;
+                self.partial_body = None;
+                self.response = Some(Arc::new(*response));
+                if self.protobuf_message.is_some() {
+                    self.decode_protobuf_response();
+                }
+                if let Some(response) = &self.response
+                    && !matches!(response.content, ResponseContent::Error(_))
+                {
+                    self.responses.push_back(ResponseEntry {
+                        id: request_id,
+                        request: request.clone(),
+                        response: response.clone(),
+                        snapshot: None,
+                    });
+                    if self.responses.len() > RESPONSE_HISTORY_LIMIT {
+                        self.responses.pop_front();
                     }
+                    self.check_snapshot(&request);
+                } else {
+                    self.snapshot_check = None;
                 }
-                drop(url.query_pairs_mut()); // Drop the mutable borrow
-
-                let mut rb = client.request(http_method, url);
-
-                // Add headers
-                for (k, v) in headers {
-                    rb = rb.header(k.as_ref(), v.as_ref());
-                }
-
-                // Add body for non-GET requests
-                if method != "GET" && !body.is_empty() {
-                    rb = rb.body(body.to_string());
-                }
-
-                let response = match rb.send().await {
-                    Ok(resp) => {
-                        let status = resp.status().as_u16();
-                        let status_text: SharedString =
-                            resp.status().canonical_reason().unwrap_or("Unknown").into();
-
-                        // Extract headers - collect first before consuming resp
-                        let headers: Vec<(SharedString, SharedString)> = resp
-                            .headers()
-                            .iter()
-                            .map(|(k, v)| {
-                                (
-                                    k.as_str().to_string().into(),
-                                    v.to_str().unwrap_or("").to_string().into(),
-                                )
-                            })
-                            .collect();
-
-                        // Get content-type and clone it
-                        let content_type = resp
-                            .headers()
-                            .get("content-type")
-                            .and_then(|v| v.to_str().ok())
-                            .map(|s| s.to_string());
-
-                        match resp.bytes().await {
-                            Ok(body_bytes) => {
-                                let elapsed_ms = start.elapsed().as_millis() as u64;
-                                let size_bytes = body_bytes.len();
-                                let content = parse_response_content(
-                                    content_type.as_deref(),
-                                    body_bytes.to_vec(),
-                                );
-
-                                Response {
-                                    status,
-                                    status_text,
-                                    headers,
-                                    content,
-                                    elapsed_ms,
-                                    size_bytes,
-                                }
-                            }
-                            Err(e) => Response::from_error(format!("Error reading body: {}", e)),
+            }
+        }
+        cx.notify();
+    }
+
+    /// Pins the response on screen to the request that produced it as `name`.
+    pub fn pin_snapshot(&mut self, name: String, cx: &mut Context<Self>) {
+        let Some(response) = self
+            .response
+            .clone()
+            .filter(|response| !matches!(response.content, ResponseContent::Error(_)))
+        else {
+            return;
+        };
+        let request = self.response_request.clone();
+        let pinned = self.snapshots.pin(&request, name, &response);
+        self.check_snapshot(&request);
+        if let Err(e) = pinned {
+            self.snapshot_error = Some(e.into());
+        }
+        cx.notify();
+    }
+
+    pub fn delete_snapshot(&mut self, snapshot: &Snapshot, cx: &mut Context<Self>) {
+        let request = self.response_request.clone();
+        self.snapshots.remove(&request, &snapshot.id);
+        self.check_snapshot(&request);
+        cx.notify();
+    }
+
+    /// Loads the snapshots of `request` and compares the last response with
+    /// the newest one.
+    fn check_snapshot(&mut self, request: &SharedString) {
+        self.snapshot_error = None;
+        let mut pinned = Vec::new();
+        for snapshot in self.snapshots.for_request(request) {
+            let loaded = self
+                .pinned
+                .iter()
+                .find(|entry| entry.snapshot.as_ref().is_some_and(|s| s.id == snapshot.id))
+                .cloned();
+            let entry = match loaded {
+                Some(entry) => entry,
+                _ => match self.snapshots.response(snapshot) {
+                    Ok(response) => {
+                        self.next_request_id += 1;
+                        ResponseEntry {
+                            id: self.next_request_id - 1,
+                            request: request.clone(),
+                            response: Arc::new(response),
+                            snapshot: Some(snapshot.clone()),
                         }
                     }
-                    Err(e) => Response::from_error(format!("Error sending request: {}", e)),
-                };
+                    Err(e) => {
+                        self.snapshot_error = Some(e.into());
+                        continue;
+                    }
+                },
+            };
+            pinned.push(entry);
+        }
+        self.pinned = pinned;
+
+        let latest = self.responses.iter().rev().find(|entry| entry.request == *request);
+        self.snapshot_check = match (self.pinned.last(), latest) {
+            (Some(snapshot), Some(latest)) => {
+                let comparison = Comparison::new(&snapshot.response, &latest.response);
+                Some(SnapshotCheck {
+                    entry_id: snapshot.id,
+                    name: snapshot.snapshot.as_ref().map(|s| s.name.clone()).unwrap_or_default().into(),
+                    differs: snapshot.response.status != latest.response.status
+                        || !comparison.body_identical(),
+                })
+            }
+            _ => None,
+        };
+    }
+
+    pub fn select_protobuf_message(&mut self, name: Option<SharedString>, cx: &mut Context<Self>) {
+        self.protobuf_message = name;
+        self.decode_protobuf_response();
+        cx.notify();
+    }
+
+    /// Re-decodes a protobuf response with the selected message type, falling
+    /// back to the schema-less wire view when it does not fit.
+    fn decode_protobuf_response(&mut self) {
+        self.protobuf_error = None;
+        let Some(response) = self.response.as_mut() else {
+            return;
+        };
+        let (ResponseContent::Decoded(BinaryFormat::Protobuf, ..), ResponseBody::Memory(bytes)) =
+            (&response.content, &response.body)
+        else {
+            return;
+        };
+        let bytes = bytes.clone();
 
+        let typed = match (&self.protobuf_message, &self.grpc_schema) {
+            (Some(name), Some(schema)) => Some(schema.decode_message(name, &bytes)),
+            (Some(name), None) => Some(Err(format!("No schema is loaded for {}", name))),
+            (None, _) => None,
+        };
+        let value = match typed {
+            Some(Ok(value)) => value,
+            Some(Err(e)) => {
+                self.protobuf_error = Some(e.into());
+                decode::decode_protobuf(&bytes).unwrap_or_default()
+            }
+            None => decode::decode_protobuf(&bytes).unwrap_or_default(),
+        };
+        Arc::make_mut(response).content = decoded_content(BinaryFormat::Protobuf, value);
+    }
+
+    pub fn connect_websocket(&mut self, cx: &mut Context<Self>) {
+        let mut url = self.url.to_string();
+        if let Some(rest) = url.strip_prefix("http") {
+            url = format!("ws{}", rest);
+        }
+        if let Ok(mut parsed) = reqwest::Url::parse(&url)
+            && !self.queries.is_empty()
+        {
+            parsed
+                .query_pairs_mut()
+                .extend_pairs(self.queries.iter().map(|(k, v)| (k.as_ref(), v.as_ref())));
+            url = parsed.to_string();
+        }
+
+        let handshake = Handshake {
+            url: url.clone(),
+            headers: self.headers.clone(),
+            protocols: self
+                .subprotocols
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+
+        let handle = RUNTIME
+            .get()
+            .expect("Runtime not initialized")
+            .handle()
+            .clone();
+
+        let (commands, command_receiver) = unbounded();
+        let (events, mut receiver) = unbounded();
+        let task = handle.spawn(async move {
+            websocket::run_session(handshake, command_receiver, &events).await;
+        });
+        let session_id = self.next_request_id;
+        self.next_request_id += 1;
+        // Replacing the session aborts the previous connection
+        self.websocket = Some(WebSocketSession::new(
+            session_id,
+            url.clone().into(),
+            commands,
+            task.abort_handle(),
+        ));
+
+        cx.spawn(move |model: WeakEntity<AppState>, cx: &mut AsyncApp| {
+            let cx = cx.clone();
+            async move {
+                while let Some(event) = receiver.next().await {
+                    let _ = cx.update(|cx| {
+                        model.update(cx, |model, cx| {
+                            model.apply_session_event(session_id, event, cx)
+                        })
+                    });
+                }
+            }
+        })
+        .detach();
+
+        self.history.push(format!("WS {}", url).into());
+        cx.notify();
+    }
+
+    pub fn disconnect_websocket(&mut self, cx: &mut Context<Self>) {
+        if let Some(session) = &mut self.websocket {
+            session.close();
+            cx.notify();
+        }
+    }
+
+    /// Encodes `input` with the selected message format and sends it, logging
+    /// encoding errors instead of sending.
+    pub fn send_websocket_message(&mut self, input: &str, cx: &mut Context<Self>) {
+        let format = self.message_format;
+        let Some(session) = &mut self.websocket else {
+            return;
+        };
+        match format.encode(input) {
+            Ok(message) => session.send(message),
+            Err(e) => session.log.push(LogEntry::info(e)),
+        }
+        cx.notify();
+    }
+
+    pub fn send_websocket_ping(&mut self, cx: &mut Context<Self>) {
+        if let Some(session) = &self.websocket {
+            session.send(tokio_tungstenite::tungstenite::Message::Ping(Default::default()));
+            cx.notify();
+        }
+    }
+
+    pub fn cycle_message_format(&mut self, cx: &mut Context<Self>) {
+        self.message_format = self.message_format.next();
+        cx.notify();
+    }
+
+    pub fn update_subprotocols(&mut self, subprotocols: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.subprotocols = subprotocols.into();
+        cx.notify();
+    }
+
+    fn apply_session_event(&mut self, session_id: u64, event: SessionEvent, cx: &mut Context<Self>) {
+        // Ignore events of sessions that were replaced
+        let Some(session) = self.websocket.as_mut().filter(|s| s.id == session_id) else {
+            return;
+        };
+        match event {
+            SessionEvent::Open { protocol } => {
+                session.status = SessionStatus::Open;
+                session.protocol = protocol;
+            }
+            SessionEvent::Log(entry) => session.log.push(entry),
+            SessionEvent::Closed => session.status = SessionStatus::Closed,
+        }
+        cx.notify();
+    }
+
+    pub fn load_proto_files(&mut self, paths: Vec<PathBuf>, cx: &mut Context<Self>) {
+        self.grpc_schema_status = Some("Compiling .proto files...".into());
+        cx.notify();
+
+        cx.spawn(move |model: WeakEntity<AppState>, cx: &mut AsyncApp| {
+            let cx = cx.clone();
+            async move {
+                let schema = cx
+                    .background_executor()
+                    .spawn(async move { GrpcSchema::from_proto_files(&paths) })
+                    .await;
+                let _ = cx.update(|cx| {
+                    model.update(cx, |model, cx| model.apply_grpc_schema(schema, cx))
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Loads the schema from the server at the current URL via gRPC reflection.
+    pub fn reflect_grpc_schema(&mut self, cx: &mut Context<Self>) {
+        self.grpc_schema_status = Some("Querying server reflection...".into());
+        cx.notify();
+
+        let endpoint = self.url.to_string();
+        let handle = RUNTIME
+            .get()
+            .expect("Runtime not initialized")
+            .handle()
+            .clone();
+        let task = handle.spawn(async move { GrpcSchema::from_reflection(&endpoint).await });
+
+        cx.spawn(move |model: WeakEntity<AppState>, cx: &mut AsyncApp| {
+            let cx = cx.clone();
+            async move {
+                let schema = task
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Reflection task failed: {}", e)));
                 let _ = cx.update(|cx| {
-                    model.update(cx, |model, cx| {
-                        model.response = Some(response);
-                        cx.notify();
-                    })
+                    model.update(cx, |model, cx| model.apply_grpc_schema(schema, cx))
                 });
             }
         })
         .detach();
+    }
+
+    fn apply_grpc_schema(&mut self, schema: Result<GrpcSchema, String>, cx: &mut Context<Self>) {
+        match schema {
+            Ok(schema) => {
+                self.grpc_schema_status = None;
+                // Keep the selection when the reloaded schema still has the method
+                if !self
+                    .grpc_method
+                    .as_ref()
+                    .is_some_and(|path| schema.methods.iter().any(|m| &m.path == path))
+                {
+                    self.grpc_method = schema.methods.first().map(|m| m.path.clone());
+                }
+                self.grpc_schema = Some(Arc::new(schema));
+                if self.protobuf_message.is_some() {
+                    self.decode_protobuf_response();
+                }
+            }
+            Err(e) => self.grpc_schema_status = Some(format!("Failed to load schema: {}", e).into()),
+        }
+        cx.notify();
+    }
+
+    pub fn select_grpc_method(&mut self, path: SharedString, cx: &mut Context<Self>) {
+        self.grpc_method = Some(path);
+        cx.notify();
+    }
+
+    /// Default JSON of the selected method's request message.
+    pub fn grpc_request_template(&self) -> Option<String> {
+        self.grpc_schema
+            .as_ref()?
+            .request_template(self.grpc_method.as_ref()?)
+    }
+
+    fn start_grpc_call(&mut self, cx: &mut Context<Self>) {
+        let (Some(schema), Some(path)) = (self.grpc_schema.clone(), self.grpc_method.clone()) else {
+            self.grpc_schema_status =
+                Some("Load .proto files or use server reflection, then pick a method.".into());
+            cx.notify();
+            return;
+        };
+
+        let spec = CallSpec {
+            endpoint: self.url.to_string(),
+            path: path.to_string(),
+            message: self.body.to_string(),
+            metadata: self.headers.clone(),
+        };
+        let handle = RUNTIME
+            .get()
+            .expect("Runtime not initialized")
+            .handle()
+            .clone();
+
+        let (events, mut receiver) = unbounded();
+        let task = handle.spawn(async move {
+            grpc::run_call(schema, spec, &events).await;
+        });
+        let call_id = self.next_request_id;
+        self.next_request_id += 1;
+        self.grpc_call = Some(GrpcCall::new(call_id, path.clone(), task.abort_handle()));
+
+        cx.spawn(move |model: WeakEntity<AppState>, cx: &mut AsyncApp| {
+            let cx = cx.clone();
+            async move {
+                while let Some(event) = receiver.next().await {
+                    let _ = cx.update(|cx| {
+                        model.update(cx, |model, cx| model.apply_call_event(call_id, event, cx))
+                    });
+                }
+            }
+        })
+        .detach();
 
         self.history
-            .push(format!("{} {}", self.method, self.url).into());
+            .push(format!("GRPC {}{}", self.url.trim_end_matches('/'), path).into());
+        cx.notify();
+    }
+
+    pub fn cancel_grpc_call(&mut self, cx: &mut Context<Self>) {
+        if let Some(call) = &mut self.grpc_call {
+            call.cancel();
+            cx.notify();
+        }
+    }
+
+    fn apply_call_event(&mut self, call_id: u64, event: CallEvent, cx: &mut Context<Self>) {
+        // Ignore events of calls that were replaced
+        if let Some(call) = self.grpc_call.as_mut().filter(|c| c.id == call_id)
+            && call.is_running()
+        {
+            call.apply(event);
+            cx.notify();
+        }
+    }
+
+    /// Identifies the current request for per-request settings.
+    fn request_key(&self) -> SharedString {
+        format!("{} {}", self.method, self.url).into()
+    }
+
+    /// Filter saved for the request that produced the response on screen.
+    pub fn json_filter(&self) -> SharedString {
+        self.json_filters
+            .get(&self.response_request)
+            .map(|filter| SharedString::from(filter.to_string()))
+            .unwrap_or_default()
+    }
+
+    pub fn update_json_filter(&mut self, filter: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let filter: SharedString = filter.into();
+        self.json_filters.set(&self.response_request, &filter);
+        cx.notify();
+    }
+
+    /// Forces a charset on the current text response, or restores detection
+    /// with `None`, and re-decodes it. The next request is detected again.
+    pub fn set_encoding_override(
+        &mut self,
+        encoding: Option<&'static Encoding>,
+        cx: &mut Context<Self>,
+    ) {
+        self.encoding_override = encoding;
+        if let Some(response) = self.response.as_mut()
+            && response.encoding.is_some()
+        {
+            let content_type = response.header("content-type").map(|ct| ct.to_string());
+            let (content, encoding) = parse_response_content(
+                content_type.as_deref(),
+                response.body.preview().clone(),
+                response.body.is_spooled(),
+                encoding,
+            );
+            let response = Arc::make_mut(response);
+            response.content = content;
+            response.encoding = encoding;
+        }
+        cx.notify();
+    }
+
+    pub fn toggle_sse_auto_reconnect(&mut self, cx: &mut Context<Self>) {
+        self.sse_auto_reconnect = !self.sse_auto_reconnect;
+        cx.notify();
+    }
+
+    pub fn update_max_in_memory_bytes(&mut self, bytes: usize, cx: &mut Context<Self>) {
+        self.max_in_memory_bytes = bytes;
+        cx.notify();
+    }
+
+    pub fn update_preview_bytes(&mut self, bytes: usize, cx: &mut Context<Self>) {
+        self.preview_bytes = bytes;
         cx.notify();
     }
 
@@ -171,4 +809,429 @@ impl AppState {
         self.queries = queries;
         cx.notify();
     }
+
+    pub fn update_environment(&mut self, environment: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let environment: SharedString = environment.into();
+        let environment: SharedString = if environment.trim().is_empty() {
+            DEFAULT_ENVIRONMENT.into()
+        } else {
+            environment.trim().to_string().into()
+        };
+        if environment != self.environment {
+            // Requests in flight keep the client, and so the jar, they were sent with
+            self.client = build_client(self.cookie_jar.environment(&environment), None)
+                .expect("Failed to create reqwest client");
+            self.environment = environment;
+        }
+        cx.notify();
+    }
+
+    /// Cookies stored in the jar of the active environment.
+    pub fn cookies(&self) -> Vec<StoredCookie> {
+        self.cookie_jar.cookies(&self.environment)
+    }
+
+    pub fn upsert_cookie(&mut self, cookie: StoredCookie, cx: &mut Context<Self>) {
+        self.cookie_jar.upsert(&self.environment, cookie);
+        cx.notify();
+    }
+
+    pub fn remove_cookie(&mut self, cookie: &StoredCookie, cx: &mut Context<Self>) {
+        self.cookie_jar.remove(&self.environment, cookie);
+        cx.notify();
+    }
+
+    pub fn clear_cookie_domain(&mut self, domain: &str, cx: &mut Context<Self>) {
+        self.cookie_jar.clear_domain(&self.environment, domain);
+        cx.notify();
+    }
+
+    pub fn clear_cookies(&mut self, cx: &mut Context<Self>) {
+        self.cookie_jar.clear(&self.environment);
+        cx.notify();
+    }
+}
+
+fn build_client(
+    cookies: Arc<EnvironmentCookies>,
+    unix_socket: Option<PathBuf>,
+) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent("gpui-app")
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(TimedResolver)
+        .connector_layer(ConnectTimingLayer)
+        .tls_info(true)
+        .cookie_provider(cookies);
+    if let Some(socket) = unix_socket {
+        #[cfg(unix)]
+        {
+            builder = builder.unix_socket(socket);
+        }
+        #[cfg(not(unix))]
+        {
+            return Err(format!(
+                "Unix sockets are not supported on this platform: {}",
+                socket.display()
+            ));
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Splits `unix:///var/run/docker.sock:/v1.43/containers/json` into the socket
+/// path and an HTTP URL for the request path, which defaults to `/`.
+fn split_unix_url(url: &str) -> Option<(PathBuf, String)> {
+    let rest = url.strip_prefix("unix://")?;
+    let (socket, path) = match rest.find(":/") {
+        Some(i) => (&rest[..i], &rest[i + 1..]),
+        None => (rest, "/"),
+    };
+    Some((PathBuf::from(socket), format!("http://localhost{}", path)))
+}
+
+/// Sends the request and follows redirects manually so that every hop can be
+/// recorded on the resulting `Response`.
+async fn execute_request(
+    client: reqwest::Client,
+    spec: RequestSpec,
+    events: &UnboundedSender<RequestEvent>,
+) -> Response {
+    let RequestSpec {
+        method,
+        url,
+        queries,
+        headers,
+        body,
+        max_in_memory_bytes,
+        preview_bytes,
+        sse_auto_reconnect,
+    } = spec;
+    let start = Instant::now();
+
+    let mut http_method = match method.as_ref() {
+        "POST" => reqwest::Method::POST,
+        "PUT" => reqwest::Method::PUT,
+        "DELETE" => reqwest::Method::DELETE,
+        "PATCH" => reqwest::Method::PATCH,
+        _ => reqwest::Method::GET,
+    };
+
+    let mut url = match reqwest::Url::parse(url.as_ref()) {
+        Ok(url) => url,
+        Err(e) => return Response::from_error(format!("Invalid URL: {}", e)),
+    };
+    if !queries.is_empty() {
+        let mut query_pairs = url.query_pairs_mut();
+        for (k, v) in queries {
+            query_pairs.append_pair(k.as_ref(), v.as_ref());
+        }
+    }
+
+    // Add body for non-GET requests
+    let mut send_body = method != "GET" && !body.is_empty();
+    let mut send_credentials = true;
+    let mut redirects = Vec::new();
+    let mut cookies = Vec::new();
+
+    let (mut resp, mut timing, final_request) = loop {
+        let hop_start = Instant::now();
+        let mut rb = client.request(http_method.clone(), url.clone());
+
+        // Add headers, dropping credentials once the chain leaves the original origin
+        for (k, v) in &headers {
+            let name = k.to_ascii_lowercase();
+            if !send_credentials && (name == AUTHORIZATION.as_str() || name == COOKIE.as_str()) {
+                continue;
+            }
+            rb = rb.header(k.as_ref(), v.as_ref());
+        }
+
+        if send_body {
+            rb = rb.body(body.to_string());
+        }
+
+        // Kept to reopen event streams; `None` only for streaming bodies, which are never sent
+        let request = rb.try_clone();
+        let (result, phases) = timing::record_phases(rb.send()).await;
+        let resp = match result {
+            Ok(resp) => resp,
+            Err(e) => return Response::from_error(format!("Error sending request: {}", e)),
+        };
+        let headers_received = hop_start.elapsed();
+        cookies.extend(collect_set_cookies(resp.headers(), &url));
+
+        let status = resp.status().as_u16();
+        let location = match status {
+            301 | 302 | 303 | 307 | 308 => resp
+                .headers()
+                .get(LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string()),
+            _ => None,
+        };
+        let Some(location) = location else {
+            let mut timing = Timings::new(phases, headers_received);
+            timing.redirect_ms = timing::as_ms(hop_start.duration_since(start));
+            break (resp, timing, request);
+        };
+
+        if redirects.len() >= MAX_REDIRECTS {
+            return Response::from_error(format!(
+                "Too many redirects (more than {})",
+                MAX_REDIRECTS
+            ));
+        }
+        let next_url = match url.join(&location) {
+            Ok(next_url) => next_url,
+            Err(e) => {
+                return Response::from_error(format!("Invalid redirect location {}: {}", location, e));
+            }
+        };
+
+        redirects.push(RedirectHop {
+            status,
+            url: url.to_string().into(),
+            location: next_url.to_string().into(),
+            headers: collect_headers(resp.headers()),
+            elapsed_ms: headers_received.as_millis() as u64,
+        });
+
+        // 301/302/303 are replayed as GET without a body, 307/308 keep both
+        if matches!(status, 301..=303) && http_method != reqwest::Method::HEAD {
+            http_method = reqwest::Method::GET;
+            send_body = false;
+        }
+        if next_url.origin() != url.origin() {
+            send_credentials = false;
+        }
+        url = next_url;
+    };
+
+    let status = resp.status().as_u16();
+    let status_text: SharedString = resp.status().canonical_reason().unwrap_or("Unknown").into();
+
+    // Extract headers - collect first before consuming resp
+    let headers = collect_headers(resp.headers());
+
+    timing.remote_addr = resp.remote_addr().map(|addr| addr.to_string().into());
+    timing.http_version = format!("{:?}", resp.version()).into();
+    timing.tls = resp
+        .extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|info| info.peer_certificate())
+        .and_then(TlsDetails::from_der);
+
+    // Get content-type and clone it
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    if content_type
+        .as_deref()
+        .is_some_and(|ct| ct.starts_with("text/event-stream"))
+    {
+        let response = Response {
+            status,
+            status_text,
+            headers,
+            content: ResponseContent::EventStream(Vec::new()),
+            body: ResponseBody::Memory(Arc::from([])),
+            elapsed_ms: start.elapsed().as_millis() as u64,
+            size_bytes: 0,
+            redirects,
+            timing,
+            cookies,
+            encoding: None,
+        };
+        let reconnect = final_request.filter(|_| sse_auto_reconnect);
+        return stream_events(resp, response, reconnect, events).await;
+    }
+
+    let mut progress = DownloadProgress::new(
+        resp.headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok()),
+    );
+    let _ = events.unbounded_send(RequestEvent::Progress(progress.clone()));
+    let mut last_progress = Instant::now();
+
+    // NDJSON records are shown one by one as their lines complete
+    let mut records = content_type
+        .as_deref()
+        .is_some_and(ndjson::is_ndjson)
+        .then(|| (NdJsonParser::default(), Vec::new()));
+    if records.is_some() {
+        let response = Response {
+            status,
+            status_text: status_text.clone(),
+            headers: headers.clone(),
+            content: ResponseContent::NdJson(Vec::new()),
+            body: ResponseBody::Memory(Arc::from([])),
+            elapsed_ms: start.elapsed().as_millis() as u64,
+            size_bytes: 0,
+            redirects: redirects.clone(),
+            timing: timing.clone(),
+            cookies: cookies.clone(),
+            encoding: None,
+        };
+        let _ = events.unbounded_send(RequestEvent::Streaming(Box::new(response)));
+    }
+
+    // Other text of unknown length (chunked) is previewed while it arrives
+    let mut live_tail = (records.is_none()
+        && progress.total.is_none()
+        && content_type
+            .as_deref()
+            .is_some_and(|ct| ct.starts_with("text/") || ct.contains("json")))
+    .then(Vec::new);
+
+    // Stream the body so large downloads report progress and spill to disk
+    let download_start = Instant::now();
+    let mut sink = BodySink::new(max_in_memory_bytes, preview_bytes);
+    loop {
+        match resp.chunk().await {
+            Ok(Some(chunk)) => {
+                if let Err(e) = sink.write(&chunk) {
+                    return Response::from_error(format!("Error buffering body: {}", e));
+                }
+                progress.received = sink.len() as u64;
+                if let Some((parser, all)) = &mut records {
+                    let parsed = parser.feed(&chunk);
+                    if !parsed.is_empty() {
+                        all.extend(parsed.iter().cloned());
+                        let _ = events.unbounded_send(RequestEvent::Records(parsed));
+                    }
+                }
+                if let Some(tail) = &mut live_tail {
+                    tail.extend_from_slice(&chunk);
+                    let excess = tail.len().saturating_sub(LIVE_TAIL_BYTES);
+                    tail.drain(..excess);
+                }
+                if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                    last_progress = Instant::now();
+                    let _ = events.unbounded_send(RequestEvent::Progress(progress.clone()));
+                    if let Some(tail) = &live_tail {
+                        let text = String::from_utf8_lossy(tail).into_owned();
+                        let _ = events.unbounded_send(RequestEvent::Partial(text.into()));
+                    }
+                }
+            }
+            Ok(None) => break,
+            Err(e) => return Response::from_error(format!("Error reading body: {}", e)),
+        }
+    }
+    timing.download_ms = timing::as_ms(download_start.elapsed());
+
+    let size_bytes = sink.len();
+    let body = match sink.finish() {
+        Ok(body) => body,
+        Err(e) => return Response::from_error(format!("Error buffering body: {}", e)),
+    };
+    let (content, encoding) = match records {
+        Some((mut parser, mut all)) => {
+            all.extend(parser.finish());
+            (ResponseContent::NdJson(all), None)
+        }
+        None => parse_response_content(
+            content_type.as_deref(),
+            body.preview().clone(),
+            body.is_spooled(),
+            None,
+        ),
+    };
+
+    Response {
+        status,
+        status_text,
+        headers,
+        content,
+        body,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+        size_bytes,
+        redirects,
+        timing,
+        cookies,
+        encoding,
+    }
+}
+
+/// Reads an event stream until it ends, forwarding each event to the UI. With
+/// `reconnect` set, a closed stream is reopened after the server's retry delay,
+/// passing the last seen id as `Last-Event-ID`.
+async fn stream_events(
+    mut resp: reqwest::Response,
+    mut response: Response,
+    reconnect: Option<reqwest::RequestBuilder>,
+    events: &UnboundedSender<RequestEvent>,
+) -> Response {
+    let _ = events.unbounded_send(RequestEvent::Streaming(Box::new(response.clone())));
+    let started = Instant::now();
+    let mut parser = SseParser::default();
+    let mut received = Vec::new();
+    let mut failures = 0;
+
+    loop {
+        // A read error ends the stream just like the server closing it
+        while let Ok(Some(chunk)) = resp.chunk().await {
+            for event in parser.feed(&chunk) {
+                response.size_bytes += event.data.len();
+                received.push(event.clone());
+                let _ = events.unbounded_send(RequestEvent::Event(event));
+            }
+        }
+
+        let Some(request) = reconnect.as_ref().and_then(|r| r.try_clone()) else {
+            break;
+        };
+        let retry = parser
+            .reconnect_ms()
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_SSE_RETRY);
+        // Back off exponentially while the server stays unreachable
+        let retry = retry.saturating_mul(1 << failures).min(MAX_SSE_RETRY);
+        tokio::time::sleep(retry).await;
+
+        let request = match parser.last_event_id() {
+            Some(id) => request.header("Last-Event-ID", id),
+            None => request,
+        };
+        match request.send().await {
+            Ok(next) if is_event_stream(&next) => {
+                failures = 0;
+                resp = next;
+            }
+            // The server refused or ended the stream (e.g. 204 No Content);
+            // stop reconnecting like a browser would
+            Ok(_) => break,
+            // Network failures are retried after the next delay, up to a limit
+            Err(_) => {
+                failures += 1;
+                if failures >= MAX_SSE_RECONNECT_FAILURES {
+                    break;
+                }
+            }
+        }
+    }
+
+    response.timing.download_ms = timing::as_ms(started.elapsed());
+    response.elapsed_ms += started.elapsed().as_millis() as u64;
+    response.content = ResponseContent::EventStream(received);
+    response
+}
+
+/// Whether a reconnection was answered with a stream to keep reading.
+fn is_event_stream(resp: &reqwest::Response) -> bool {
+    resp.status().is_success()
+        && resp.status() != reqwest::StatusCode::NO_CONTENT
+        && resp
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"))
 }
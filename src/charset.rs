@@ -0,0 +1,123 @@
+use encoding_rs::Encoding;
+use std::borrow::Cow;
+
+/// Encodings offered for the manual override, by label.
+pub const OVERRIDE_ENCODINGS: &[&str] = &[
+    "UTF-8",
+    "UTF-16LE",
+    "UTF-16BE",
+    "windows-1252",
+    "ISO-8859-2",
+    "ISO-8859-15",
+    "windows-1251",
+    "KOI8-R",
+    "Shift_JIS",
+    "EUC-JP",
+    "ISO-2022-JP",
+    "GBK",
+    "gb18030",
+    "Big5",
+    "EUC-KR",
+];
+
+/// How far into the body an HTML or XML encoding declaration is looked for.
+const DECLARATION_SCAN_BYTES: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodingSource {
+    Bom,
+    /// The `charset` parameter of `Content-Type`.
+    Header,
+    /// `<meta charset>` or `<?xml encoding?>` in the body.
+    Document,
+    /// Nothing declared; UTF-8 assumed.
+    Default,
+    Manual,
+}
+
+/// The encoding a text body was decoded with and where it came from.
+#[derive(Clone, Copy, Debug)]
+pub struct TextEncoding {
+    pub encoding: &'static Encoding,
+    pub source: EncodingSource,
+}
+
+impl TextEncoding {
+    pub fn name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    pub fn source_label(&self) -> &'static str {
+        match self.source {
+            EncodingSource::Bom => "BOM",
+            EncodingSource::Header => "Content-Type",
+            EncodingSource::Document => "document",
+            EncodingSource::Default => "default",
+            EncodingSource::Manual => "manual",
+        }
+    }
+}
+
+pub fn for_label(label: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(label.trim().trim_matches(['"', '\'']).as_bytes())
+}
+
+/// Picks the encoding of a text body: the override, then a byte order mark,
+/// then the `charset` header parameter, then a declaration in the document.
+pub fn detect(
+    content_type: Option<&str>,
+    bytes: &[u8],
+    manual: Option<&'static Encoding>,
+) -> TextEncoding {
+    let (encoding, source) = if let Some(encoding) = manual {
+        (encoding, EncodingSource::Manual)
+    } else if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        (encoding, EncodingSource::Bom)
+    } else if let Some(encoding) = content_type.and_then(header_charset) {
+        (encoding, EncodingSource::Header)
+    } else if let Some(encoding) = document_charset(bytes) {
+        (encoding, EncodingSource::Document)
+    } else {
+        (encoding_rs::UTF_8, EncodingSource::Default)
+    };
+    TextEncoding { encoding, source }
+}
+
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| for_label(value))
+            .flatten()
+    })
+}
+
+/// Finds `charset=` (HTML meta tags) or `encoding=` (XML declaration) near
+/// the start of the document.
+fn document_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(DECLARATION_SCAN_BYTES)];
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+    if !head.trim_start().starts_with('<') {
+        return None;
+    }
+    ["charset=", "encoding="].iter().find_map(|key| {
+        let start = head.find(key)? + key.len();
+        let value = head[start..].trim_start_matches(['"', '\'', ' ']);
+        let end = value
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+            .unwrap_or(value.len());
+        // UTF-16 cannot be declared from inside an ASCII-compatible document
+        for_label(&value[..end]).map(|encoding| encoding.output_encoding())
+    })
+}
+
+/// Decodes `bytes`, skipping a BOM that matches the encoding. Malformed
+/// sequences become U+FFFD.
+pub fn decode<'a>(bytes: &'a [u8], encoding: &'static Encoding) -> Cow<'a, str> {
+    let bytes = match Encoding::for_bom(bytes) {
+        Some((bom_encoding, len)) if bom_encoding == encoding => &bytes[len..],
+        _ => bytes,
+    };
+    encoding.decode_without_bom_handling(bytes).0
+}
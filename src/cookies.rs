@@ -0,0 +1,261 @@
+use cookie::time::OffsetDateTime;
+use reqwest::header::HeaderValue;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+pub const DEFAULT_ENVIRONMENT: &str = "default";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+    /// Unix timestamp in seconds; `None` for session cookies.
+    pub expires: Option<i64>,
+    pub secure: bool,
+    pub http_only: bool,
+    /// Set when the cookie had no `Domain` attribute and only matches its origin host.
+    pub host_only: bool,
+}
+
+impl StoredCookie {
+    /// Parses a `Set-Cookie` value as received from `url`. Returns `None` for
+    /// malformed cookies and for domains the response is not allowed to set.
+    pub fn from_set_cookie(header: &str, url: &reqwest::Url) -> Option<Self> {
+        let parsed = cookie::Cookie::parse(header).ok()?;
+        let host = url.host_str()?.to_ascii_lowercase();
+
+        let (domain, host_only) = match parsed.domain() {
+            Some(domain) if !domain.is_empty() => {
+                let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+                if !domain_matches(&host, &domain) {
+                    return None;
+                }
+                (domain, false)
+            }
+            _ => (host, true),
+        };
+
+        let path = match parsed.path() {
+            Some(path) if path.starts_with('/') => path.to_string(),
+            _ => default_path(url.path()),
+        };
+
+        // Max-Age takes precedence over Expires
+        let expires = match parsed.max_age() {
+            Some(max_age) => Some((OffsetDateTime::now_utc() + max_age).unix_timestamp()),
+            None => parsed.expires_datetime().map(|at| at.unix_timestamp()),
+        };
+
+        Some(Self {
+            name: parsed.name().to_string(),
+            value: parsed.value().to_string(),
+            domain,
+            path,
+            expires,
+            secure: parsed.secure().unwrap_or(false),
+            http_only: parsed.http_only().unwrap_or(false),
+            host_only,
+        })
+    }
+
+    pub fn expires_label(&self) -> String {
+        self.expires
+            .and_then(|expires| OffsetDateTime::from_unix_timestamp(expires).ok())
+            .map(|expires| expires.to_string())
+            .unwrap_or_else(|| "Session".to_string())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires
+            .is_some_and(|expires| expires <= OffsetDateTime::now_utc().unix_timestamp())
+    }
+
+    fn matches(&self, url: &reqwest::Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+        domain_ok
+            && path_matches(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+            && !self.is_expired()
+    }
+
+    fn same_identity(&self, other: &StoredCookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/')
+                || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+fn default_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(i) => request_path[..i].to_string(),
+    }
+}
+
+/// Cookies of every environment, persisted as JSON in the user's data directory.
+#[derive(Default, Serialize, Deserialize)]
+struct JarData {
+    environments: BTreeMap<String, Vec<StoredCookie>>,
+}
+
+/// Cookies of every environment, shared by the HTTP clients of all of them.
+pub struct CookieJar {
+    data: RwLock<JarData>,
+    path: Option<PathBuf>,
+}
+
+impl CookieJar {
+    pub fn load() -> Self {
+        let path = dirs::data_dir().map(|dir| dir.join("resty").join("cookies.json"));
+        let data = path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+        Self {
+            data: RwLock::new(data),
+            path,
+        }
+    }
+
+    /// The jar of `environment` as a cookie store for an HTTP client.
+    pub fn environment(self: &Arc<Self>, environment: &str) -> Arc<EnvironmentCookies> {
+        Arc::new(EnvironmentCookies {
+            jar: self.clone(),
+            environment: environment.to_string(),
+        })
+    }
+
+    /// All live cookies of `environment`, sorted by domain then name.
+    pub fn cookies(&self, environment: &str) -> Vec<StoredCookie> {
+        let data = self.data.read().unwrap();
+        let mut cookies: Vec<StoredCookie> = data
+            .environments
+            .get(environment)
+            .map(|cookies| cookies.iter().filter(|c| !c.is_expired()).cloned().collect())
+            .unwrap_or_default();
+        cookies.sort_by(|a, b| a.domain.cmp(&b.domain).then_with(|| a.name.cmp(&b.name)));
+        cookies
+    }
+
+    /// Inserts `cookie`, replacing any cookie with the same name, domain and path.
+    pub fn upsert(&self, environment: &str, cookie: StoredCookie) {
+        self.modify(environment, |cookies| {
+            cookies.retain(|c| !c.same_identity(&cookie));
+            if !cookie.is_expired() {
+                cookies.push(cookie);
+            }
+        });
+    }
+
+    pub fn remove(&self, environment: &str, cookie: &StoredCookie) {
+        self.modify(environment, |cookies| cookies.retain(|c| !c.same_identity(cookie)));
+    }
+
+    pub fn clear_domain(&self, environment: &str, domain: &str) {
+        self.modify(environment, |cookies| cookies.retain(|c| c.domain != domain));
+    }
+
+    pub fn clear(&self, environment: &str) {
+        self.modify(environment, Vec::clear);
+    }
+
+    fn modify(&self, environment: &str, f: impl FnOnce(&mut Vec<StoredCookie>)) {
+        {
+            let mut data = self.data.write().unwrap();
+            let cookies = data.environments.entry(environment.to_string()).or_default();
+            f(cookies);
+            cookies.retain(|c| !c.is_expired());
+        }
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let Ok(json) = serde_json::to_vec_pretty(&*self.data.read().unwrap()) else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Cookie store of one environment. It is fixed when the client is built, so
+/// responses still in flight after switching environments store their cookies
+/// where the request was sent from.
+pub struct EnvironmentCookies {
+    jar: Arc<CookieJar>,
+    environment: String,
+}
+
+impl reqwest::cookie::CookieStore for EnvironmentCookies {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &reqwest::Url) {
+        let cookies: Vec<StoredCookie> = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| StoredCookie::from_set_cookie(value, url))
+            .collect();
+        if cookies.is_empty() {
+            return;
+        }
+
+        self.jar.modify(&self.environment, |stored| {
+            for cookie in cookies {
+                stored.retain(|c| !c.same_identity(&cookie));
+                if !cookie.is_expired() {
+                    stored.push(cookie);
+                }
+            }
+        });
+    }
+
+    fn cookies(&self, url: &reqwest::Url) -> Option<HeaderValue> {
+        let data = self.jar.data.read().unwrap();
+        let mut matching: Vec<&StoredCookie> = data
+            .environments
+            .get(&self.environment)?
+            .iter()
+            .filter(|c| c.matches(url))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+
+        // More specific paths first, as browsers do
+        matching.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+        let header = matching
+            .iter()
+            .map(|c| format!("{}={}", c.name, c.value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        HeaderValue::from_str(&header).ok()
+    }
+}
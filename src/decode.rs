@@ -0,0 +1,284 @@
+use serde_json::{Map, Number, Value};
+
+/// Nesting level past which length-delimited fields are no longer tried as
+/// messages, so hostile bodies cannot exhaust the stack.
+const MAX_MESSAGE_DEPTH: usize = 64;
+
+/// Binary encodings that are decoded into a JSON tree for display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryFormat {
+    Protobuf,
+    MessagePack,
+    Cbor,
+}
+
+impl BinaryFormat {
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/x-protobuf"
+            | "application/protobuf"
+            | "application/x-google-protobuf"
+            | "application/vnd.google.protobuf" => Some(BinaryFormat::Protobuf),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(BinaryFormat::MessagePack)
+            }
+            "application/cbor" => Some(BinaryFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BinaryFormat::Protobuf => "Protobuf",
+            BinaryFormat::MessagePack => "MessagePack",
+            BinaryFormat::Cbor => "CBOR",
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> Result<Value, String> {
+        match self {
+            BinaryFormat::Protobuf => decode_protobuf(bytes),
+            BinaryFormat::MessagePack => decode_msgpack(bytes),
+            BinaryFormat::Cbor => decode_cbor(bytes),
+        }
+    }
+}
+
+/// Bytes that are not text, shown as hex.
+fn bytes_value(bytes: &[u8]) -> Value {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Value::String(format!("0x{}", hex))
+}
+
+fn float_value(value: f64) -> Value {
+    Number::from_f64(value).map_or(Value::Null, Value::Number)
+}
+
+/// Map keys that are not strings are shown in their JSON form.
+fn key_string(key: Value) -> String {
+    match key {
+        Value::String(key) => key,
+        other => other.to_string(),
+    }
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or("truncated varint")?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("varint is longer than 10 bytes".to_string())
+}
+
+fn read_fixed<const N: usize>(bytes: &[u8], pos: &mut usize) -> Result<[u8; N], String> {
+    let end = *pos + N;
+    let fixed = bytes
+        .get(*pos..end)
+        .ok_or("truncated fixed-width field")?
+        .try_into()
+        .map_err(|_| "truncated fixed-width field")?;
+    *pos = end;
+    Ok(fixed)
+}
+
+/// Decodes protobuf wire format without a schema, like `protoc --decode_raw`.
+/// Fields are keyed by number and repeated numbers become arrays. Nested
+/// messages are recognized when a length-delimited field parses as one,
+/// otherwise it is shown as a string or as hex.
+pub fn decode_protobuf(bytes: &[u8]) -> Result<Value, String> {
+    decode_message(bytes, 0)
+}
+
+fn decode_message(bytes: &[u8], depth: usize) -> Result<Value, String> {
+    let mut fields = Map::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let key = read_varint(bytes, &mut pos)?;
+        let number = key >> 3;
+        if number == 0 {
+            return Err(format!("invalid field number 0 at offset {}", pos));
+        }
+        let value = match key & 7 {
+            0 => Value::from(read_varint(bytes, &mut pos)?),
+            1 => {
+                let raw = read_fixed::<8>(bytes, &mut pos)?;
+                Value::from(u64::from_le_bytes(raw))
+            }
+            2 => {
+                let len = read_varint(bytes, &mut pos)? as usize;
+                let end = pos.checked_add(len).filter(|&end| end <= bytes.len());
+                let data = &bytes[pos..end.ok_or("length-delimited field overruns the body")?];
+                pos += len;
+                length_delimited(data, depth)
+            }
+            5 => {
+                let raw = read_fixed::<4>(bytes, &mut pos)?;
+                Value::from(u32::from_le_bytes(raw))
+            }
+            wire_type => {
+                return Err(format!(
+                    "unsupported wire type {} for field {}",
+                    wire_type, number
+                ));
+            }
+        };
+
+        match fields.get_mut(&number.to_string()) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+            None => {
+                fields.insert(number.to_string(), value);
+            }
+        }
+    }
+    Ok(Value::Object(fields))
+}
+
+fn length_delimited(data: &[u8], depth: usize) -> Value {
+    if !data.is_empty()
+        && depth < MAX_MESSAGE_DEPTH
+        && let Ok(message) = decode_message(data, depth + 1)
+    {
+        return message;
+    }
+    match std::str::from_utf8(data) {
+        Ok(text) if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+            Value::String(text.to_string())
+        }
+        _ => bytes_value(data),
+    }
+}
+
+pub fn decode_msgpack(bytes: &[u8]) -> Result<Value, String> {
+    fn convert(value: rmpv::Value) -> Value {
+        match value {
+            rmpv::Value::Nil => Value::Null,
+            rmpv::Value::Boolean(flag) => Value::Bool(flag),
+            rmpv::Value::Integer(int) => int
+                .as_u64()
+                .map(Value::from)
+                .or_else(|| int.as_i64().map(Value::from))
+                .unwrap_or(Value::Null),
+            rmpv::Value::F32(float) => float_value(float.into()),
+            rmpv::Value::F64(float) => float_value(float),
+            rmpv::Value::String(text) => match text.into_str() {
+                Some(text) => Value::String(text),
+                None => Value::Null,
+            },
+            rmpv::Value::Binary(bytes) => bytes_value(&bytes),
+            rmpv::Value::Array(items) => Value::Array(items.into_iter().map(convert).collect()),
+            rmpv::Value::Map(entries) => Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key_string(convert(key)), convert(value)))
+                    .collect(),
+            ),
+            rmpv::Value::Ext(kind, data) => {
+                serde_json::json!({ "ext": kind, "data": bytes_value(&data) })
+            }
+        }
+    }
+
+    let mut reader = bytes;
+    let value = rmpv::decode::read_value(&mut reader).map_err(|e| e.to_string())?;
+    if !reader.is_empty() {
+        return Err(format!("{} trailing bytes after the value", reader.len()));
+    }
+    Ok(convert(value))
+}
+
+pub fn decode_cbor(bytes: &[u8]) -> Result<Value, String> {
+    fn convert(value: ciborium::Value) -> Value {
+        match value {
+            ciborium::Value::Null => Value::Null,
+            ciborium::Value::Bool(flag) => Value::Bool(flag),
+            ciborium::Value::Integer(int) => {
+                let int = i128::from(int);
+                u64::try_from(int)
+                    .map(Value::from)
+                    .or_else(|_| i64::try_from(int).map(Value::from))
+                    .unwrap_or_else(|_| Value::String(int.to_string()))
+            }
+            ciborium::Value::Float(float) => float_value(float),
+            ciborium::Value::Text(text) => Value::String(text),
+            ciborium::Value::Bytes(bytes) => bytes_value(&bytes),
+            ciborium::Value::Tag(tag, value) => {
+                serde_json::json!({ "tag": tag, "value": convert(*value) })
+            }
+            ciborium::Value::Array(items) => Value::Array(items.into_iter().map(convert).collect()),
+            ciborium::Value::Map(entries) => Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key_string(convert(key)), convert(value)))
+                    .collect(),
+            ),
+            _ => Value::Null,
+        }
+    }
+
+    let value: ciborium::Value = ciborium::from_reader(bytes).map_err(|e| e.to_string())?;
+    Ok(convert(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn varint(mut value: usize, out: &mut Vec<u8>) {
+        while value >= 0x80 {
+            out.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    /// `levels` messages each holding the next one in field 1, around `{1: 1}`.
+    fn nested(levels: usize) -> Vec<u8> {
+        let mut message = vec![0x08, 0x01];
+        for _ in 0..levels {
+            let mut outer = vec![0x0a];
+            varint(message.len(), &mut outer);
+            outer.extend(message);
+            message = outer;
+        }
+        message
+    }
+
+    fn depth(value: &Value) -> (usize, &Value) {
+        let mut depth = 0;
+        let mut value = value;
+        while let Some(inner) = value.as_object().and_then(|fields| fields.get("1")) {
+            depth += 1;
+            value = inner;
+        }
+        (depth, value)
+    }
+
+    #[test]
+    fn decodes_fields() {
+        let mut bytes = vec![0x08, 0x96, 0x01, 0x12, 0x05];
+        bytes.extend(b"hello");
+        let value = decode_protobuf(&bytes).unwrap();
+        assert_eq!(value, serde_json::json!({ "1": 150, "2": "hello" }));
+    }
+
+    #[test]
+    fn decodes_nesting_up_to_the_cap() {
+        let value = decode_protobuf(&nested(MAX_MESSAGE_DEPTH)).unwrap();
+        assert_eq!(depth(&value), (MAX_MESSAGE_DEPTH + 1, &Value::from(1)));
+    }
+
+    #[test]
+    fn stops_decoding_past_the_cap() {
+        let value = decode_protobuf(&nested(MAX_MESSAGE_DEPTH + 100)).unwrap();
+        let (levels, leaf) = depth(&value);
+        assert_eq!(levels, MAX_MESSAGE_DEPTH + 1);
+        assert!(leaf.is_string());
+    }
+}
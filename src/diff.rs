@@ -0,0 +1,321 @@
+use crate::json_tree::path_segment;
+use crate::response::{Response, ResponseBody, ResponseContent};
+use gpui::SharedString;
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+use std::io::Read;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Line diffs give up on finding the smallest diff after this long.
+const LINE_DIFF_TIMEOUT: Duration = Duration::from_millis(500);
+/// Longest value shown in a JSON change row.
+const MAX_VALUE_CHARS: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A JSON path or header whose value differs between two responses.
+#[derive(Clone, Debug)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// JSONPath of the value, or the header name.
+    pub path: SharedString,
+    pub left: Option<SharedString>,
+    pub right: Option<SharedString>,
+}
+
+impl Change {
+    fn new(path: String, left: Option<String>, right: Option<String>) -> Self {
+        let kind = match (&left, &right) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Removed,
+            _ => ChangeKind::Changed,
+        };
+        Self {
+            kind,
+            path: path.into(),
+            left: left.map(Into::into),
+            right: right.map(Into::into),
+        }
+    }
+}
+
+/// Paths that were added, removed or changed from `left` to `right`. Objects
+/// are compared by key and arrays by index; a value that changes type is one
+/// change at its path.
+pub fn json_diff(left: &Value, right: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_values(left, right, "$".to_string(), &mut changes);
+    changes
+}
+
+fn diff_values(left: &Value, right: &Value, path: String, changes: &mut Vec<Change>) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            for (key, left_value) in left {
+                let child = format!("{}{}", path, path_segment(key));
+                match right.get(key) {
+                    Some(right_value) => diff_values(left_value, right_value, child, changes),
+                    None => changes.push(Change::new(child, Some(show(left_value)), None)),
+                }
+            }
+            for (key, right_value) in right {
+                if !left.contains_key(key) {
+                    let child = format!("{}{}", path, path_segment(key));
+                    changes.push(Change::new(child, None, Some(show(right_value))));
+                }
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for ix in 0..left.len().max(right.len()) {
+                let child = format!("{}[{}]", path, ix);
+                match (left.get(ix), right.get(ix)) {
+                    (Some(l), Some(r)) => diff_values(l, r, child, changes),
+                    (l, r) => changes.push(Change::new(child, l.map(show), r.map(show))),
+                }
+            }
+        }
+        _ if left != right => changes.push(Change::new(path, Some(show(left)), Some(show(right)))),
+        _ => {}
+    }
+}
+
+fn show(value: &Value) -> String {
+    let text = value.to_string();
+    match text.char_indices().nth(MAX_VALUE_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// Header changes, matching names case-insensitively. Repeated headers are
+/// compared as their values joined in order.
+pub fn header_diff(
+    left: &[(SharedString, SharedString)],
+    right: &[(SharedString, SharedString)],
+) -> Vec<Change> {
+    fn joined(headers: &[(SharedString, SharedString)]) -> Vec<(String, String)> {
+        let mut merged: Vec<(String, String)> = Vec::new();
+        for (name, value) in headers {
+            let name = name.to_ascii_lowercase();
+            match merged.iter_mut().find(|(existing, _)| *existing == name) {
+                Some((_, values)) => {
+                    values.push_str(", ");
+                    values.push_str(value);
+                }
+                None => merged.push((name, value.to_string())),
+            }
+        }
+        merged
+    }
+
+    let left = joined(left);
+    let right = joined(right);
+    let mut changes = Vec::new();
+    for (name, value) in &left {
+        match right.iter().find(|(other, _)| other == name) {
+            Some((_, other)) if other == value => {}
+            other => changes.push(Change::new(
+                name.clone(),
+                Some(value.clone()),
+                other.map(|(_, other)| other.clone()),
+            )),
+        }
+    }
+    for (name, value) in &right {
+        if !left.iter().any(|(other, _)| other == name) {
+            changes.push(Change::new(name.clone(), None, Some(value.clone())));
+        }
+    }
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineTag {
+    Equal,
+    Removed,
+    Added,
+}
+
+/// One line of a unified diff with its 1-based numbers on either side.
+#[derive(Clone, Debug)]
+pub struct DiffLine {
+    pub tag: LineTag,
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+    pub text: SharedString,
+}
+
+/// Unified line diff of two texts.
+pub fn line_diff(left: &str, right: &str) -> Vec<DiffLine> {
+    let diff = TextDiff::configure()
+        .timeout(LINE_DIFF_TIMEOUT)
+        .diff_lines(left, right);
+    diff.iter_all_changes()
+        .map(|change| {
+            let text = change.value();
+            let text = text.strip_suffix('\n').unwrap_or(text);
+            DiffLine {
+                tag: match change.tag() {
+                    ChangeTag::Equal => LineTag::Equal,
+                    ChangeTag::Delete => LineTag::Removed,
+                    ChangeTag::Insert => LineTag::Added,
+                },
+                left: change.old_index().map(|ix| ix + 1),
+                right: change.new_index().map(|ix| ix + 1),
+                text: text.strip_suffix('\r').unwrap_or(text).to_string().into(),
+            }
+        })
+        .collect()
+}
+
+/// How the bodies of two responses differ.
+pub enum BodyDiff {
+    /// Both bodies are JSON (or decoded binary) and were compared structurally.
+    Json(Vec<Change>),
+    Lines(Vec<DiffLine>),
+    /// At least one body is not text or was spooled to disk; only whether
+    /// the bytes match is known.
+    Bytes { identical: bool },
+}
+
+/// Everything that differs between two responses, computed once per pair.
+pub struct Comparison {
+    pub headers: Vec<Change>,
+    pub body: BodyDiff,
+}
+
+impl Comparison {
+    pub fn new(left: &Response, right: &Response) -> Self {
+        // Parsed content of a spooled body covers only its preview, so the
+        // full bodies are compared instead
+        let spooled = left.body.is_spooled() || right.body.is_spooled();
+        let body = match (json_value(left), json_value(right)) {
+            _ if spooled => BodyDiff::Bytes {
+                identical: bodies_equal(&left.body, &right.body).unwrap_or(false),
+            },
+            (Some(l), Some(r)) => BodyDiff::Json(json_diff(l, r)),
+            _ => match (body_text(left), body_text(right)) {
+                (Some(l), Some(r)) => BodyDiff::Lines(line_diff(l, r)),
+                _ => BodyDiff::Bytes {
+                    identical: left.body.preview() == right.body.preview(),
+                },
+            },
+        };
+        Self {
+            headers: header_diff(&left.headers, &right.headers),
+            body,
+        }
+    }
+
+    /// Whether the bodies match. Headers are left out since dates and
+    /// request ids differ on every run.
+    pub fn body_identical(&self) -> bool {
+        match &self.body {
+            BodyDiff::Json(changes) => changes.is_empty(),
+            BodyDiff::Lines(lines) => lines.iter().all(|line| line.tag == LineTag::Equal),
+            BodyDiff::Bytes { identical } => *identical,
+        }
+    }
+}
+
+/// Whether two bodies hold the same bytes, read in chunks so spooled bodies
+/// are not loaded whole.
+fn bodies_equal(left: &ResponseBody, right: &ResponseBody) -> std::io::Result<bool> {
+    if left.len()? != right.len()? {
+        return Ok(false);
+    }
+    let (mut left, mut right) = (left.reader()?, right.reader()?);
+    let mut left_buf = vec![0; 64 * 1024];
+    let mut right_buf = vec![0; 64 * 1024];
+    loop {
+        let n = fill(&mut left, &mut left_buf)?;
+        if fill(&mut right, &mut right_buf)? != n || left_buf[..n] != right_buf[..n] {
+            return Ok(false);
+        }
+        if n == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Reads until `buf` is full or the reader ends, returning the bytes read.
+fn fill(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..])? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    Ok(read)
+}
+
+fn json_value(response: &Response) -> Option<&Arc<Value>> {
+    match &response.content {
+        ResponseContent::Json(_, value) | ResponseContent::Decoded(_, _, value) => Some(value),
+        _ => None,
+    }
+}
+
+fn body_text(response: &Response) -> Option<&str> {
+    match &response.content {
+        ResponseContent::Text(text)
+        | ResponseContent::Json(text, _)
+        | ResponseContent::Decoded(_, text, _)
+        | ResponseContent::Xml(text)
+        | ResponseContent::Html(text, _) => Some(text),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn text_response(body: ResponseBody, preview: &str) -> Response {
+        Response {
+            content: ResponseContent::Text(preview.to_string().into()),
+            body,
+            ..Response::from_error(String::new())
+        }
+    }
+
+    fn spooled(bytes: &[u8], preview: &[u8]) -> ResponseBody {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(bytes).unwrap();
+        ResponseBody::Spooled {
+            file: Arc::new(file.into_temp_path()),
+            preview: preview.into(),
+        }
+    }
+
+    #[test]
+    fn spooled_bodies_with_equal_previews_differ_past_them() {
+        let left = text_response(spooled(b"head-tail-a", b"head"), "head");
+        let right = text_response(spooled(b"head-tail-b", b"head"), "head");
+        assert!(!Comparison::new(&left, &right).body_identical());
+    }
+
+    #[test]
+    fn spooled_body_matches_the_same_bytes_in_memory() {
+        let bytes = vec![7u8; 200 * 1024];
+        let left = text_response(spooled(&bytes, &bytes[..16]), "");
+        let right = text_response(ResponseBody::Memory(bytes.into()), "");
+        assert!(Comparison::new(&left, &right).body_identical());
+    }
+
+    #[test]
+    fn spooled_body_differs_from_its_own_preview() {
+        let left = text_response(spooled(b"head-tail", b"head"), "head");
+        let right = text_response(ResponseBody::Memory(b"head".as_slice().into()), "head");
+        assert!(!Comparison::new(&left, &right).body_identical());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::response::ResponseBody;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tempfile::NamedTempFile;
+
+/// Default in-memory cap above which response bodies are spooled to disk.
+pub const DEFAULT_MAX_IN_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+/// Default amount of a spooled body that is kept in memory and rendered as a preview.
+pub const DEFAULT_PREVIEW_BYTES: usize = 64 * 1024;
+
+#[derive(Clone, Debug)]
+pub struct DownloadProgress {
+    pub received: u64,
+    /// From `Content-Length`, when the server sent one.
+    pub total: Option<u64>,
+    pub started: Instant,
+}
+
+impl DownloadProgress {
+    pub fn new(total: Option<u64>) -> Self {
+        Self {
+            received: 0,
+            total,
+            started: Instant::now(),
+        }
+    }
+
+    /// Bytes per second since the download started.
+    pub fn rate(&self) -> f64 {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.received as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.total?.saturating_sub(self.received);
+        let rate = self.rate();
+        (rate > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / rate))
+    }
+
+    pub fn fraction(&self) -> Option<f32> {
+        self.total
+            .filter(|total| *total > 0)
+            .map(|total| (self.received as f64 / total as f64).min(1.0) as f32)
+    }
+}
+
+/// Collects a response body in memory and switches to a temporary file once
+/// it grows past `max_in_memory` bytes, keeping its first `preview` bytes.
+pub struct BodySink {
+    max_in_memory: usize,
+    preview: usize,
+    buffer: Vec<u8>,
+    spool: Option<NamedTempFile>,
+    len: usize,
+}
+
+impl BodySink {
+    pub fn new(max_in_memory: usize, preview: usize) -> Self {
+        Self {
+            max_in_memory,
+            preview,
+            buffer: Vec::new(),
+            spool: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn write(&mut self, chunk: &[u8]) -> std::io::Result<()> {
+        self.len += chunk.len();
+        if let Some(spool) = &mut self.spool {
+            let keep = self.preview.saturating_sub(self.buffer.len()).min(chunk.len());
+            self.buffer.extend_from_slice(&chunk[..keep]);
+            return spool.write_all(chunk);
+        }
+
+        self.buffer.extend_from_slice(chunk);
+        if self.buffer.len() > self.max_in_memory {
+            let mut spool = NamedTempFile::with_prefix("resty-")?;
+            spool.write_all(&self.buffer)?;
+            self.buffer.truncate(self.preview);
+            self.buffer.shrink_to_fit();
+            self.spool = Some(spool);
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> std::io::Result<ResponseBody> {
+        let preview: Arc<[u8]> = self.buffer.into();
+        match self.spool {
+            Some(mut spool) => {
+                spool.flush()?;
+                Ok(ResponseBody::Spooled {
+                    file: Arc::new(spool.into_temp_path()),
+                    preview,
+                })
+            }
+            None => Ok(ResponseBody::Memory(preview)),
+        }
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}h {}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
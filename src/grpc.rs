@@ -0,0 +1,484 @@
+use base64::Engine as _;
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use futures::channel::mpsc::UnboundedSender;
+use gpui::SharedString;
+use prost_reflect::prost::Message as _;
+use prost_reflect::prost::bytes::Buf;
+use prost_reflect::prost_types::FileDescriptorProto;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor, SerializeOptions};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::time::Instant;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{AsciiMetadataKey, BinaryMetadataKey, KeyAndValueRef, MetadataMap, MetadataValue};
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+
+/// Decodes `-bin` metadata values; gRPC senders may omit the padding.
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+#[derive(Clone, Debug)]
+pub struct GrpcMethod {
+    /// Request path, e.g. `/helloworld.Greeter/SayHello`.
+    pub path: SharedString,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
+
+impl GrpcMethod {
+    pub fn kind(&self) -> &'static str {
+        match (self.client_streaming, self.server_streaming) {
+            (false, false) => "unary",
+            (false, true) => "server streaming",
+            (true, false) => "client streaming",
+            (true, true) => "bidi streaming",
+        }
+    }
+}
+
+/// Services and messages known from `.proto` files or server reflection.
+pub struct GrpcSchema {
+    /// Where the descriptors came from, shown next to the method list.
+    pub source: SharedString,
+    pub methods: Vec<GrpcMethod>,
+    pool: DescriptorPool,
+}
+
+impl GrpcSchema {
+    fn new(source: impl Into<SharedString>, pool: DescriptorPool) -> Self {
+        let methods = pool
+            .services()
+            .filter(|service| !service.full_name().starts_with("grpc.reflection."))
+            .flat_map(|service| service.methods().collect::<Vec<_>>())
+            .map(|method| GrpcMethod {
+                path: method_path(&method).into(),
+                client_streaming: method.is_client_streaming(),
+                server_streaming: method.is_server_streaming(),
+            })
+            .collect();
+        Self {
+            source: source.into(),
+            methods,
+            pool,
+        }
+    }
+
+    /// Compiles `.proto` files, resolving imports relative to their directories.
+    pub fn from_proto_files(paths: &[PathBuf]) -> Result<Self, String> {
+        let includes: BTreeSet<PathBuf> = paths
+            .iter()
+            .filter_map(|path| path.parent().map(PathBuf::from))
+            .collect();
+        let files = protox::compile(paths, includes).map_err(|e| e.to_string())?;
+        let pool = DescriptorPool::from_file_descriptor_set(files).map_err(|e| e.to_string())?;
+
+        let source = match paths {
+            [path] => path.display().to_string(),
+            _ => format!("{} .proto files", paths.len()),
+        };
+        Ok(Self::new(source, pool))
+    }
+
+    /// Fetches the descriptors of every service the server exposes through the
+    /// `grpc.reflection.v1` service.
+    pub async fn from_reflection(endpoint: &str) -> Result<Self, String> {
+        let mut client = ServerReflectionClient::new(connect(endpoint).await?);
+
+        let services = match reflect(&mut client, MessageRequest::ListServices(String::new())).await? {
+            MessageResponse::ListServicesResponse(list) => list.service,
+            _ => return Err("Unexpected reflection response".to_string()),
+        };
+
+        let mut requests: Vec<MessageRequest> = services
+            .into_iter()
+            .filter(|service| !service.name.starts_with("grpc.reflection."))
+            .map(|service| MessageRequest::FileContainingSymbol(service.name))
+            .collect();
+        let mut files: BTreeMap<String, FileDescriptorProto> = BTreeMap::new();
+        let mut requested = BTreeSet::new();
+        while let Some(request) = requests.pop() {
+            let MessageResponse::FileDescriptorResponse(response) = reflect(&mut client, request).await?
+            else {
+                return Err("Unexpected reflection response".to_string());
+            };
+            for bytes in response.file_descriptor_proto {
+                let file = FileDescriptorProto::decode(bytes.as_slice()).map_err(|e| e.to_string())?;
+                files.insert(file.name().to_string(), file);
+            }
+
+            // Servers may leave out imports they expect us to know; ask for them by name
+            if requests.is_empty() {
+                let missing: BTreeSet<String> = files
+                    .values()
+                    .flat_map(|file| file.dependency.iter().cloned())
+                    .filter(|dependency| !files.contains_key(dependency))
+                    .filter(|dependency| requested.insert(dependency.clone()))
+                    .collect();
+                requests.extend(missing.into_iter().map(MessageRequest::FileByFilename));
+            }
+        }
+
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_protos(files.into_values())
+            .map_err(|e| e.to_string())?;
+        Ok(Self::new(format!("Reflection: {}", endpoint), pool))
+    }
+
+    fn method(&self, path: &str) -> Option<MethodDescriptor> {
+        self.pool
+            .services()
+            .flat_map(|service| service.methods().collect::<Vec<_>>())
+            .find(|method| method_path(method) == path)
+    }
+
+    /// Full names of the messages in the schema, for decoding response bodies.
+    pub fn message_names(&self) -> Vec<SharedString> {
+        let mut names: Vec<SharedString> = self
+            .pool
+            .all_messages()
+            .filter(|message| !message.is_map_entry())
+            .map(|message| message.full_name().to_string().into())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Decodes `bytes` as the message `name` and converts it to JSON.
+    pub fn decode_message(&self, name: &str, bytes: &[u8]) -> Result<serde_json::Value, String> {
+        let descriptor = self
+            .pool
+            .get_message_by_name(name)
+            .ok_or_else(|| format!("Unknown message type {}", name))?;
+        let message = DynamicMessage::decode(descriptor, bytes).map_err(|e| e.to_string())?;
+        let options = SerializeOptions::new().skip_default_fields(false);
+        message
+            .serialize_with_options(serde_json::value::Serializer, &options)
+            .map_err(|e| e.to_string())
+    }
+
+    /// The request message of `path` with every field at its default, as JSON.
+    pub fn request_template(&self, path: &str) -> Option<String> {
+        let message = DynamicMessage::new(self.method(path)?.input());
+        let options = SerializeOptions::new().skip_default_fields(false);
+        let mut json = Vec::new();
+        let mut serializer = serde_json::Serializer::pretty(&mut json);
+        message.serialize_with_options(&mut serializer, &options).ok()?;
+        String::from_utf8(json).ok()
+    }
+}
+
+fn method_path(method: &MethodDescriptor) -> String {
+    format!("/{}/{}", method.parent_service().full_name(), method.name())
+}
+
+async fn reflect(
+    client: &mut ServerReflectionClient<Channel>,
+    request: MessageRequest,
+) -> Result<MessageResponse, String> {
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(request),
+    };
+    let mut responses = client
+        .server_reflection_info(futures::stream::iter([request]))
+        .await
+        .map_err(|status| status.message().to_string())?
+        .into_inner();
+    let response = responses
+        .message()
+        .await
+        .map_err(|status| status.message().to_string())?
+        .and_then(|response| response.message_response)
+        .ok_or_else(|| "Empty reflection response".to_string())?;
+    match response {
+        MessageResponse::ErrorResponse(error) => Err(error.error_message),
+        response => Ok(response),
+    }
+}
+
+async fn connect(endpoint: &str) -> Result<Channel, String> {
+    let mut channel = Endpoint::from_shared(endpoint.to_string()).map_err(|e| error_chain(&e))?;
+    if endpoint.starts_with("https://") {
+        channel = channel
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| error_chain(&e))?;
+    }
+    channel.connect().await.map_err(|e| error_chain(&e))
+}
+
+/// Transport errors only say "transport error" at the top; include the causes.
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Encodes and decodes `DynamicMessage`s for methods only known at runtime.
+struct DynamicCodec(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicCodec;
+    type Decoder = DynamicCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicCodec(self.0.clone())
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicCodec(self.0.clone())
+    }
+}
+
+impl Encoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = tonic::Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.reserve(item.encoded_len());
+        item.encode(dst)
+            .map_err(|e| tonic::Status::internal(e.to_string()))
+    }
+}
+
+impl Decoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = tonic::Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let bytes = src.copy_to_bytes(src.remaining());
+        DynamicMessage::decode(self.0.clone(), bytes)
+            .map(Some)
+            .map_err(|e| tonic::Status::internal(e.to_string()))
+    }
+}
+
+/// Final gRPC status of a call.
+#[derive(Clone, Debug)]
+pub struct GrpcStatus {
+    pub code: i32,
+    pub name: SharedString,
+    pub message: SharedString,
+}
+
+impl GrpcStatus {
+    fn ok() -> Self {
+        Self::from_code(tonic::Code::Ok, "")
+    }
+
+    fn from_code(code: tonic::Code, message: impl Into<String>) -> Self {
+        Self {
+            code: code as i32,
+            name: format!("{:?}", code).into(),
+            message: message.into().into(),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+}
+
+/// Updates sent from the call task back to the UI.
+pub enum CallEvent {
+    Headers(Vec<(SharedString, SharedString)>),
+    Message(SharedString),
+    Finished {
+        status: GrpcStatus,
+        trailers: Vec<(SharedString, SharedString)>,
+    },
+}
+
+/// A unary or server-streaming call and everything received for it so far.
+pub struct GrpcCall {
+    pub id: u64,
+    pub path: SharedString,
+    pub headers: Vec<(SharedString, SharedString)>,
+    /// Response messages as pretty-printed JSON.
+    pub messages: Vec<SharedString>,
+    pub trailers: Vec<(SharedString, SharedString)>,
+    /// `None` while the call is in flight.
+    pub status: Option<GrpcStatus>,
+    pub started: Instant,
+    pub elapsed_ms: Option<u64>,
+    task: tokio::task::AbortHandle,
+}
+
+impl GrpcCall {
+    pub fn new(id: u64, path: SharedString, task: tokio::task::AbortHandle) -> Self {
+        Self {
+            id,
+            path,
+            headers: Vec::new(),
+            messages: Vec::new(),
+            trailers: Vec::new(),
+            status: None,
+            started: Instant::now(),
+            elapsed_ms: None,
+            task,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status.is_none()
+    }
+
+    pub fn apply(&mut self, event: CallEvent) {
+        match event {
+            CallEvent::Headers(headers) => self.headers = headers,
+            CallEvent::Message(message) => self.messages.push(message),
+            CallEvent::Finished { status, trailers } => {
+                self.status = Some(status);
+                self.trailers = trailers;
+                self.elapsed_ms = Some(self.started.elapsed().as_millis() as u64);
+            }
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if self.is_running() {
+            self.task.abort();
+            self.apply(CallEvent::Finished {
+                status: GrpcStatus::from_code(tonic::Code::Cancelled, "Cancelled by user"),
+                trailers: Vec::new(),
+            });
+        }
+    }
+}
+
+impl Drop for GrpcCall {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Everything needed to make one call, detached from the UI state.
+pub struct CallSpec {
+    pub endpoint: String,
+    pub path: String,
+    pub message: String,
+    pub metadata: Vec<(SharedString, SharedString)>,
+}
+
+pub async fn run_call(
+    schema: std::sync::Arc<GrpcSchema>,
+    spec: CallSpec,
+    events: &UnboundedSender<CallEvent>,
+) {
+    let (status, trailers) = match call(&schema, spec, events).await {
+        Ok(finished) => finished,
+        Err(status) => (status, Vec::new()),
+    };
+    let _ = events.unbounded_send(CallEvent::Finished { status, trailers });
+}
+
+type Finished = (GrpcStatus, Vec<(SharedString, SharedString)>);
+
+async fn call(
+    schema: &GrpcSchema,
+    spec: CallSpec,
+    events: &UnboundedSender<CallEvent>,
+) -> Result<Finished, GrpcStatus> {
+    let invalid = |message: String| GrpcStatus::from_code(tonic::Code::InvalidArgument, message);
+
+    let method = schema
+        .method(&spec.path)
+        .ok_or_else(|| invalid(format!("Unknown method {}", spec.path)))?;
+    if method.is_client_streaming() {
+        return Err(GrpcStatus::from_code(
+            tonic::Code::Unimplemented,
+            "Client and bidi streaming calls are not supported",
+        ));
+    }
+
+    let source = if spec.message.trim().is_empty() { "{}" } else { spec.message.as_str() };
+    let mut deserializer = serde_json::Deserializer::from_str(source);
+    let message = DynamicMessage::deserialize(method.input(), &mut deserializer)
+        .map_err(|e| invalid(format!("Invalid request message: {}", e)))?;
+
+    let mut request = tonic::Request::new(message);
+    for (key, value) in &spec.metadata {
+        let name = key.to_ascii_lowercase();
+        let invalid_key = |_| invalid(format!("Invalid metadata key {}", key));
+        let invalid_value = || invalid(format!("Invalid metadata value for {}", key));
+        // `-bin` keys carry raw bytes, entered base64-encoded
+        if name.ends_with("-bin") {
+            let key = BinaryMetadataKey::from_bytes(name.as_bytes()).map_err(invalid_key)?;
+            let bytes = BASE64.decode(value.trim()).map_err(|_| invalid_value())?;
+            request.metadata_mut().insert_bin(key, MetadataValue::from_bytes(&bytes));
+        } else {
+            let key = AsciiMetadataKey::from_bytes(name.as_bytes()).map_err(invalid_key)?;
+            let value = MetadataValue::try_from(value.as_ref()).map_err(|_| invalid_value())?;
+            request.metadata_mut().insert(key, value);
+        }
+    }
+    let path = PathAndQuery::try_from(spec.path.as_str())
+        .map_err(|e| invalid(format!("Invalid method path: {}", e)))?;
+
+    let unavailable = |message: String| GrpcStatus::from_code(tonic::Code::Unavailable, message);
+    let mut grpc = tonic::client::Grpc::new(connect(&spec.endpoint).await.map_err(unavailable)?);
+    grpc.ready().await.map_err(|e| unavailable(error_chain(&e)))?;
+
+    // Unary calls share the wire format of server streaming; going through the
+    // streaming API keeps trailers apart from the response headers.
+    let response = match grpc
+        .server_streaming(request, path, DynamicCodec(method.output()))
+        .await
+    {
+        Ok(response) => response,
+        Err(status) => return Ok((to_status(&status), metadata_pairs(status.metadata()))),
+    };
+    let _ = events.unbounded_send(CallEvent::Headers(metadata_pairs(response.metadata())));
+
+    let mut stream = response.into_inner();
+    loop {
+        match stream.message().await {
+            Ok(Some(message)) => {
+                let json = serde_json::to_string_pretty(&message)
+                    .unwrap_or_else(|e| format!("Failed to render message: {}", e));
+                let _ = events.unbounded_send(CallEvent::Message(json.into()));
+            }
+            Ok(None) => break,
+            Err(status) => return Ok((to_status(&status), metadata_pairs(status.metadata()))),
+        }
+    }
+    let trailers = match stream.trailers().await {
+        Ok(trailers) => trailers.as_ref().map(metadata_pairs).unwrap_or_default(),
+        Err(status) => return Ok((to_status(&status), metadata_pairs(status.metadata()))),
+    };
+    Ok((GrpcStatus::ok(), trailers))
+}
+
+fn to_status(status: &tonic::Status) -> GrpcStatus {
+    GrpcStatus::from_code(status.code(), status.message())
+}
+
+fn metadata_pairs(metadata: &MetadataMap) -> Vec<(SharedString, SharedString)> {
+    metadata
+        .iter()
+        .map(|entry| match entry {
+            KeyAndValueRef::Ascii(key, value) => (
+                key.as_str().to_string().into(),
+                crate::headers::value_text(value.as_encoded_bytes()).into(),
+            ),
+            KeyAndValueRef::Binary(key, value) => (
+                key.as_str().to_string().into(),
+                format!("{:?}", value).into(),
+            ),
+        })
+        .collect()
+}
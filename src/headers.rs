@@ -0,0 +1,300 @@
+use gpui::SharedString;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc2822;
+
+/// A header value as text. Values that are not UTF-8 keep their printable
+/// ASCII and show every other byte as `\xNN`.
+pub fn value_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes
+            .iter()
+            .map(|&byte| match byte {
+                b'\\' => "\\\\".to_string(),
+                b' '..=b'~' => (byte as char).to_string(),
+                _ => format!("\\x{:02x}", byte),
+            })
+            .collect(),
+    }
+}
+
+/// One-line explanation of a standard response header, for tooltips.
+pub fn description(name: &str) -> Option<&'static str> {
+    let description = match name.to_ascii_lowercase().as_str() {
+        "accept-ranges" => "Whether the server supports range requests, and in which unit.",
+        "access-control-allow-credentials" => "Whether the response may be shared when credentials are included.",
+        "access-control-allow-headers" => "Request headers allowed in the actual CORS request.",
+        "access-control-allow-methods" => "Methods allowed in the actual CORS request.",
+        "access-control-allow-origin" => "Origins allowed to read the response (CORS).",
+        "access-control-expose-headers" => "Response headers that scripts on other origins may read.",
+        "access-control-max-age" => "Seconds a CORS preflight result may be cached.",
+        "age" => "Seconds the response has been in a proxy cache.",
+        "allow" => "Methods supported by the resource.",
+        "alt-svc" => "Alternative services (e.g. HTTP/3) the origin is reachable at.",
+        "cache-control" => "Caching directives for browsers and shared caches.",
+        "connection" => "Whether the connection stays open after this response.",
+        "content-disposition" => "Whether the body is shown inline or downloaded, and its file name.",
+        "content-encoding" => "Compression applied to the body.",
+        "content-language" => "Language of the intended audience.",
+        "content-length" => "Size of the body in bytes.",
+        "content-location" => "Alternate location of the returned data.",
+        "content-range" => "Where a partial body belongs in the full resource.",
+        "content-security-policy" => "Sources the page may load content from.",
+        "content-security-policy-report-only" => "Content policy that is reported but not enforced.",
+        "content-type" => "Media type and charset of the body.",
+        "cross-origin-embedder-policy" => "Which cross-origin resources the document may embed.",
+        "cross-origin-opener-policy" => "Whether the window shares a browsing context group with cross-origin documents.",
+        "cross-origin-resource-policy" => "Which origins may load this resource.",
+        "date" => "When the response was generated.",
+        "etag" => "Version identifier of the resource, for conditional requests.",
+        "expires" => "When the response becomes stale.",
+        "keep-alive" => "Timeout and maximum requests of a persistent connection.",
+        "last-modified" => "When the resource last changed.",
+        "link" => "Links to related resources, e.g. pagination or preloads.",
+        "location" => "URL to redirect to, or of a newly created resource.",
+        "permissions-policy" => "Browser features the page may use.",
+        "pragma" => "Legacy HTTP/1.0 caching directive.",
+        "ratelimit" | "ratelimit-limit" | "x-ratelimit-limit" => "Request quota of the current window.",
+        "ratelimit-policy" => "Quota policies the server applies.",
+        "ratelimit-remaining" | "x-ratelimit-remaining" => "Requests left in the current window.",
+        "ratelimit-reset" | "x-ratelimit-reset" => "When the current rate-limit window resets.",
+        "referrer-policy" => "How much referrer information is sent with requests.",
+        "retry-after" => "How long to wait before making another request.",
+        "server" => "Software used by the origin server.",
+        "server-timing" => "Server-side timing metrics for the request.",
+        "set-cookie" => "Cookie the client should store.",
+        "strict-transport-security" => "Only connect to this host over HTTPS for the given time.",
+        "timing-allow-origin" => "Origins allowed to see resource timing details.",
+        "trailer" => "Headers sent after the body in a chunked message.",
+        "transfer-encoding" => "Encoding used to transfer the body, e.g. chunked.",
+        "upgrade" => "Protocol the connection switches to.",
+        "vary" => "Request headers that select between cached variants.",
+        "via" => "Proxies the response passed through.",
+        "www-authenticate" => "Authentication scheme required to access the resource.",
+        "x-content-type-options" => "`nosniff` stops browsers guessing the content type.",
+        "x-frame-options" => "Whether the page may be shown in a frame.",
+        "x-powered-by" => "Framework or language running the server.",
+        "x-request-id" | "x-correlation-id" => "Identifier for tracing this request in server logs.",
+        "x-xss-protection" => "Legacy cross-site scripting filter setting.",
+        _ => return None,
+    };
+    Some(description)
+}
+
+/// A labelled part of a structured header value.
+#[derive(Clone, Debug)]
+pub struct HeaderField {
+    pub label: SharedString,
+    pub value: SharedString,
+}
+
+fn field(label: impl Into<SharedString>, value: impl Into<SharedString>) -> HeaderField {
+    HeaderField {
+        label: label.into(),
+        value: value.into(),
+    }
+}
+
+/// Splits structured headers into their parts. Empty for headers without a
+/// known structure.
+pub fn decode(name: &str, value: &str) -> Vec<HeaderField> {
+    match name.to_ascii_lowercase().as_str() {
+        "set-cookie" => set_cookie(value),
+        "cache-control" => cache_control(value),
+        "link" => link(value),
+        "content-security-policy" | "content-security-policy-report-only" => csp(value),
+        "server-timing" => server_timing(value),
+        "ratelimit" | "ratelimit-policy" => parameters(value),
+        "ratelimit-reset" | "x-ratelimit-reset" | "x-rate-limit-reset" | "retry-after" => {
+            reset_time(value).into_iter().collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn duration_label(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m {}s", seconds / 60, seconds % 60),
+        3600..86400 => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+        _ => format!("{}d {}h", seconds / 86400, seconds % 86400 / 3600),
+    }
+}
+
+fn set_cookie(value: &str) -> Vec<HeaderField> {
+    let mut parts = value.split(';');
+    let Some((name, cookie_value)) = parts.next().and_then(|pair| pair.split_once('=')) else {
+        return Vec::new();
+    };
+    let mut fields = vec![field(name.trim().to_string(), cookie_value.trim().to_string())];
+    fields.extend(parts.filter(|part| !part.trim().is_empty()).map(|part| {
+        match part.split_once('=') {
+            Some((attribute, value)) => field(attribute.trim().to_string(), value.trim().to_string()),
+            None => field(part.trim().to_string(), "set"),
+        }
+    }));
+    fields
+}
+
+fn cache_control(value: &str) -> Vec<HeaderField> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| match directive.split_once('=') {
+            Some((name, value)) => {
+                let value = unquote(value);
+                let value = match value.parse::<u64>() {
+                    Ok(seconds) if name.trim().ends_with("age") || name.contains("stale") => {
+                        format!("{} ({})", value, duration_label(seconds))
+                    }
+                    _ => value.to_string(),
+                };
+                field(name.trim().to_string(), value)
+            }
+            None => {
+                let meaning = match directive.to_ascii_lowercase().as_str() {
+                    "no-cache" => "revalidate before every use",
+                    "no-store" => "never cache",
+                    "no-transform" => "proxies must not modify the body",
+                    "must-revalidate" => "revalidate once stale",
+                    "proxy-revalidate" => "shared caches revalidate once stale",
+                    "private" => "only the browser may cache",
+                    "public" => "any cache may store",
+                    "immutable" => "never changes while fresh",
+                    _ => "set",
+                };
+                field(directive.to_string(), meaning)
+            }
+        })
+        .collect()
+}
+
+fn link(value: &str) -> Vec<HeaderField> {
+    // Links are separated by commas, which may also appear inside `<...>`
+    let mut links = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>').map(|end| start + end) else {
+            break;
+        };
+        let url = &rest[start + 1..end];
+        let params_end = rest[end..].find('<').map_or(rest.len(), |next| end + next);
+        let params = &rest[end + 1..params_end];
+        let rel = params
+            .split(';')
+            .filter_map(|param| param.split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("rel"))
+            .map_or("link".to_string(), |(_, rel)| unquote(rel).to_string());
+        links.push(field(rel, url.to_string()));
+        rest = &rest[params_end..];
+    }
+    links
+}
+
+fn csp(value: &str) -> Vec<HeaderField> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| match directive.split_once(char::is_whitespace) {
+            Some((name, sources)) => field(name.to_string(), sources.trim().to_string()),
+            None => field(directive.to_string(), ""),
+        })
+        .collect()
+}
+
+fn server_timing(value: &str) -> Vec<HeaderField> {
+    value
+        .split(',')
+        .filter_map(|metric| {
+            let mut params = metric.split(';');
+            let name = params.next()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            let mut duration = None;
+            let mut description = None;
+            for param in params {
+                match param.split_once('=') {
+                    Some((key, value)) if key.trim() == "dur" => duration = Some(unquote(value).to_string()),
+                    Some((key, value)) if key.trim() == "desc" => {
+                        description = Some(unquote(value).to_string())
+                    }
+                    _ => {}
+                }
+            }
+            let value = match (duration, description) {
+                (Some(duration), Some(description)) => format!("{}ms {}", duration, description),
+                (Some(duration), None) => format!("{}ms", duration),
+                (None, Some(description)) => description,
+                (None, None) => String::new(),
+            };
+            Some(field(name.to_string(), value))
+        })
+        .collect()
+}
+
+/// `key=value` parameters of the IETF `RateLimit` headers, e.g.
+/// `"default";q=100;w=60` or `limit=100, remaining=50, reset=30`.
+fn parameters(value: &str) -> Vec<HeaderField> {
+    value
+        .split([',', ';'])
+        .filter_map(|part| {
+            let part = part.trim();
+            match part.split_once('=') {
+                Some((key, value)) => {
+                    let label = match key.trim() {
+                        "q" => "quota",
+                        "w" => "window (s)",
+                        "r" => "remaining",
+                        "t" => "reset (s)",
+                        "qu" => "quota unit",
+                        key => key,
+                    };
+                    Some(field(label.to_string(), unquote(value).to_string()))
+                }
+                None if !part.is_empty() => Some(field("policy", unquote(part).to_string())),
+                None => None,
+            }
+        })
+        .collect()
+}
+
+/// When a rate-limit window resets: seconds from now, a Unix timestamp, or an
+/// HTTP date.
+fn reset_time(value: &str) -> Option<HeaderField> {
+    let value = value.trim();
+    if let Ok(number) = value.parse::<i64>() {
+        // Large numbers are Unix timestamps, small ones are delays
+        return Some(if number > 1_000_000_000 {
+            let at = OffsetDateTime::from_unix_timestamp(number).ok()?;
+            let from_now = number - OffsetDateTime::now_utc().unix_timestamp();
+            field(
+                "resets at",
+                format!(
+                    "{} ({})",
+                    at.format(&Rfc2822).ok()?,
+                    if from_now >= 0 {
+                        format!("in {}", duration_label(from_now as u64))
+                    } else {
+                        "passed".to_string()
+                    }
+                ),
+            )
+        } else {
+            field("resets in", duration_label(number.max(0) as u64))
+        });
+    }
+    OffsetDateTime::parse(value, &Rfc2822)
+        .ok()
+        .map(|at| field("resets at", at.format(&Rfc2822).unwrap_or_default()))
+}
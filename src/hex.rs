@@ -0,0 +1,144 @@
+use gpui::SharedString;
+
+pub const BYTES_PER_LINE: usize = 16;
+
+/// Number of dump lines needed for `len` bytes.
+pub fn line_count(len: usize) -> usize {
+    len.div_ceil(BYTES_PER_LINE)
+}
+
+/// Printable ASCII for the right-hand column, `.` for everything else.
+pub fn ascii(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+/// Parses a jump target: `0x1f0` (hex) or `496` (decimal).
+pub fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Space-separated hex of `bytes`, for copying a selection.
+pub fn to_hex(bytes: &[u8]) -> SharedString {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into()
+}
+
+/// Names the format of a body from its leading magic number.
+pub fn sniff(bytes: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"PK\x03\x04", "ZIP archive"),
+        (b"PK\x05\x06", "ZIP archive (empty)"),
+        (b"\x1f\x8b", "gzip"),
+        (b"BZh", "bzip2"),
+        (b"\xfd7zXZ\x00", "xz"),
+        (b"\x28\xb5\x2f\xfd", "Zstandard"),
+        (b"7z\xbc\xaf\x27\x1c", "7-Zip archive"),
+        (b"Rar!\x1a\x07", "RAR archive"),
+        (b"%PDF-", "PDF document"),
+        (b"\x89PNG\r\n\x1a\n", "PNG image"),
+        (b"\xff\xd8\xff", "JPEG image"),
+        (b"GIF87a", "GIF image"),
+        (b"GIF89a", "GIF image"),
+        (b"BM", "BMP image"),
+        (b"\x00\x00\x01\x00", "ICO image"),
+        (b"OggS", "Ogg media"),
+        (b"fLaC", "FLAC audio"),
+        (b"ID3", "MP3 audio"),
+        (b"\x1a\x45\xdf\xa3", "Matroska/WebM video"),
+        (b"\x00asm", "WebAssembly module"),
+        (b"\x7fELF", "ELF executable"),
+        (b"MZ", "Windows executable"),
+        (b"\xca\xfe\xba\xbe", "Java class / Mach-O universal binary"),
+        (b"\xcf\xfa\xed\xfe", "Mach-O executable"),
+        (b"SQLite format 3\x00", "SQLite database"),
+        (b"wOFF", "WOFF font"),
+        (b"wOF2", "WOFF2 font"),
+        (b"\x00\x01\x00\x00\x00", "TrueType font"),
+        (b"OTTO", "OpenType font"),
+        (b"PAR1", "Parquet file"),
+        (b"ARROW1", "Arrow file"),
+        (b"\xef\xbb\xbf", "UTF-8 text with BOM"),
+        (b"\xff\xfe", "UTF-16LE text"),
+        (b"\xfe\xff", "UTF-16BE text"),
+    ];
+
+    if let Some((_, name)) = SIGNATURES.iter().find(|(magic, _)| bytes.starts_with(magic)) {
+        return Some(name);
+    }
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" {
+        return match &bytes[8..12] {
+            b"WEBP" => Some("WebP image"),
+            b"WAVE" => Some("WAV audio"),
+            b"AVI " => Some("AVI video"),
+            _ => Some("RIFF container"),
+        };
+    }
+    if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        return match &bytes[8..12] {
+            b"avif" | b"avis" => Some("AVIF image"),
+            b"heic" | b"heix" | b"mif1" => Some("HEIF image"),
+            b"qt  " => Some("QuickTime video"),
+            _ => Some("MP4 media"),
+        };
+    }
+    if bytes.len() >= 262 && &bytes[257..262] == b"ustar" {
+        return Some("tar archive");
+    }
+    if looks_like_protobuf(bytes) {
+        return Some("Protocol Buffers (probably)");
+    }
+    None
+}
+
+/// Whether `bytes` parse as a sequence of protobuf wire-format fields.
+fn looks_like_protobuf(bytes: &[u8]) -> bool {
+    fn varint(bytes: &[u8], pos: &mut usize) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *bytes.get(*pos)?;
+            *pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    if bytes.len() < 2 {
+        return false;
+    }
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let Some(key) = varint(bytes, &mut pos) else {
+            return false;
+        };
+        if key >> 3 == 0 || key >> 3 > 536_870_911 {
+            return false;
+        }
+        let skip = match key & 7 {
+            0 => varint(bytes, &mut pos).map(|_| 0),
+            1 => Some(8),
+            2 => varint(bytes, &mut pos),
+            5 => Some(4),
+            _ => None,
+        };
+        match skip.and_then(|skip| pos.checked_add(skip as usize)) {
+            Some(end) if end <= bytes.len() => pos = end,
+            _ => return false,
+        }
+    }
+    true
+}
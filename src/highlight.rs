@@ -0,0 +1,271 @@
+use crate::theme::Theme;
+use gpui::{HighlightStyle, TextRun, TextStyle};
+use std::ops::Range;
+
+/// Languages the tokenizer understands. XML and HTML share the markup rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    Json,
+    Markup,
+    JavaScript,
+}
+
+impl Language {
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or("").trim();
+        if mime.contains("json") {
+            Some(Language::Json)
+        } else if mime.contains("xml") || mime.contains("html") {
+            Some(Language::Markup)
+        } else if mime.contains("javascript") || mime.contains("ecmascript") {
+            Some(Language::JavaScript)
+        } else {
+            None
+        }
+    }
+
+    /// Guesses the language of an untyped text such as a request body.
+    pub fn detect(text: &str) -> Option<Self> {
+        match text.trim_start().as_bytes().first() {
+            Some(b'{' | b'[') => Some(Language::Json),
+            Some(b'<') => Some(Language::Markup),
+            _ => None,
+        }
+    }
+}
+
+/// Highlight styles by byte range, as taken by `StyledText::with_highlights`.
+pub type Highlights = Vec<(Range<usize>, HighlightStyle)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Key,
+    String,
+    Number,
+    /// Booleans, null and language keywords.
+    Keyword,
+    Tag,
+    Attribute,
+    Comment,
+}
+
+const JS_KEYWORDS: &[&str] = &[
+    "async", "await", "break", "case", "catch", "class", "const", "continue", "default", "delete",
+    "do", "else", "export", "extends", "false", "finally", "for", "from", "function", "if",
+    "import", "in", "instanceof", "let", "new", "null", "of", "return", "switch", "this",
+    "throw", "true", "try", "typeof", "undefined", "var", "void", "while", "yield",
+];
+
+/// Splits `text` into highlighted tokens. Ranges are byte offsets that always
+/// fall on ASCII characters, so they are valid char boundaries.
+pub fn tokenize(text: &str, language: Language) -> Vec<(Range<usize>, TokenKind)> {
+    match language {
+        Language::Json | Language::JavaScript => tokenize_code(text, language),
+        Language::Markup => tokenize_markup(text),
+    }
+}
+
+/// Tokens as highlight styles for a `StyledText`.
+pub fn highlights(tokens: Vec<(Range<usize>, TokenKind)>, theme: &Theme) -> Highlights {
+    tokens
+        .into_iter()
+        .map(|(range, kind)| {
+            let style = HighlightStyle {
+                color: Some(theme.syntax_color(kind)),
+                ..Default::default()
+            };
+            (range, style)
+        })
+        .collect()
+}
+
+/// Text runs covering all of `text`, for shaping text by hand.
+pub fn text_runs(
+    text: &str,
+    language: Option<Language>,
+    base: &TextStyle,
+    theme: &Theme,
+) -> Vec<TextRun> {
+    let Some(language) = language else {
+        return vec![base.to_run(text.len())];
+    };
+
+    let mut runs = Vec::new();
+    let mut offset = 0;
+    for (range, kind) in tokenize(text, language) {
+        if range.start > offset {
+            runs.push(base.to_run(range.start - offset));
+        }
+        let mut run = base.to_run(range.len());
+        run.color = theme.syntax_color(kind);
+        runs.push(run);
+        offset = range.end;
+    }
+    if offset < text.len() {
+        runs.push(base.to_run(text.len() - offset));
+    }
+    runs
+}
+
+fn is_ident_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b'$'
+}
+
+fn is_ident(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$'
+}
+
+/// Whether the next non-whitespace byte after `offset` is a colon.
+fn followed_by_colon(bytes: &[u8], offset: usize) -> bool {
+    bytes[offset..]
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|&b| b == b':')
+}
+
+/// End of a string literal opened by the quote at `start`, past the closing quote.
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return i + 1,
+            b'\n' if quote != b'`' => return i,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn tokenize_code(text: &str, language: Language) -> Vec<(Range<usize>, TokenKind)> {
+    let bytes = text.as_bytes();
+    let javascript = language == Language::JavaScript;
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let start = i;
+        let kind = match byte {
+            b'"' | b'\'' | b'`' if javascript || byte == b'"' => {
+                i = string_end(bytes, i).min(bytes.len());
+                if followed_by_colon(bytes, i) && byte != b'`' {
+                    TokenKind::Key
+                } else {
+                    TokenKind::String
+                }
+            }
+            b'/' if javascript && bytes.get(i + 1) == Some(&b'/') => {
+                i = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                TokenKind::Comment
+            }
+            b'/' if javascript && bytes.get(i + 1) == Some(&b'*') => {
+                i = text[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+                TokenKind::Comment
+            }
+            b'0'..=b'9' => {
+                i += 1;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric()
+                        || bytes[i] == b'.'
+                        || (matches!(bytes[i], b'+' | b'-')
+                            && matches!(bytes[i - 1], b'e' | b'E')))
+                {
+                    i += 1;
+                }
+                TokenKind::Number
+            }
+            b if is_ident_start(b) => {
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+                let word = &text[start..i];
+                if javascript && followed_by_colon(bytes, i) {
+                    TokenKind::Key
+                } else if matches!(word, "true" | "false" | "null")
+                    || (javascript && JS_KEYWORDS.contains(&word))
+                {
+                    TokenKind::Keyword
+                } else {
+                    continue;
+                }
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        // A leading minus sign belongs to the number
+        let start = if kind == TokenKind::Number && start > 0 && bytes[start - 1] == b'-' {
+            start - 1
+        } else {
+            start
+        };
+        tokens.push((start..i, kind));
+    }
+    tokens
+}
+
+fn tokenize_markup(text: &str) -> Vec<(Range<usize>, TokenKind)> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+
+        if text[i..].starts_with("<!--") {
+            let end = text[i + 4..].find("-->").map_or(bytes.len(), |n| i + n + 7);
+            tokens.push((i..end, TokenKind::Comment));
+            i = end;
+            continue;
+        }
+        if text[i..].starts_with("<![CDATA[") {
+            let end = text[i..].find("]]>").map_or(bytes.len(), |n| i + n + 3);
+            tokens.push((i..end, TokenKind::String));
+            i = end;
+            continue;
+        }
+
+        // Tag name, after `<`, `</`, `<?` or `<!`
+        i += 1;
+        if matches!(bytes.get(i), Some(b'/' | b'?' | b'!')) {
+            i += 1;
+        }
+        let name_start = i;
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() && !matches!(bytes[i], b'>' | b'/' | b'?') {
+            i += 1;
+        }
+        if i == name_start {
+            continue;
+        }
+        tokens.push((name_start..i, TokenKind::Tag));
+
+        // Attributes until the end of the tag
+        while i < bytes.len() && bytes[i] != b'>' {
+            let byte = bytes[i];
+            if byte == b'"' || byte == b'\'' {
+                let end = text[i + 1..].find(byte as char).map_or(bytes.len(), |n| i + n + 2);
+                tokens.push((i..end, TokenKind::String));
+                i = end;
+            } else if byte.is_ascii_whitespace() || matches!(byte, b'=' | b'/' | b'?') {
+                i += 1;
+            } else {
+                let start = i;
+                while i < bytes.len()
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(bytes[i], b'=' | b'>' | b'/' | b'"' | b'\'')
+                {
+                    i += 1;
+                }
+                tokens.push((start..i, TokenKind::Attribute));
+            }
+        }
+        i += 1;
+    }
+    tokens
+}
@@ -0,0 +1,111 @@
+use gpui::{Image, ImageFormat, SharedString};
+use image::{DynamicImage, ImageDecoder, ImageReader};
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// EXIF fields longer than this (maker notes, thumbnails) are left out.
+const MAX_EXIF_VALUE_CHARS: usize = 120;
+
+/// What is known about an image body before it is drawn.
+#[derive(Clone, Debug)]
+pub struct ImageInfo {
+    pub format: SharedString,
+    pub width: u32,
+    pub height: u32,
+    pub color: SharedString,
+    pub exif: Vec<(SharedString, SharedString)>,
+}
+
+/// An image body decoded once, ready to hand to `img`.
+#[derive(Clone)]
+pub struct DecodedImage {
+    pub image: Arc<Image>,
+    pub info: ImageInfo,
+}
+
+/// Reads the size, color type and EXIF data of an image body. Formats GPUI
+/// cannot draw are converted to PNG.
+pub fn decode(bytes: &[u8], mime_type: &str) -> Result<DecodedImage, String> {
+    let essence = mime_type.split(';').next().unwrap_or("").trim();
+    if essence.eq_ignore_ascii_case("image/svg+xml") {
+        return decode_svg(bytes);
+    }
+
+    let reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    let format = reader
+        .format()
+        .ok_or_else(|| format!("Unrecognized image data for {}", essence))?;
+    let mut decoder = reader.into_decoder().map_err(|e| e.to_string())?;
+    let (width, height) = decoder.dimensions();
+    let color = format!("{:?}", decoder.color_type());
+    let exif = decoder
+        .exif_metadata()
+        .ok()
+        .flatten()
+        .map(exif_fields)
+        .unwrap_or_default();
+
+    let drawable = match format {
+        image::ImageFormat::Png => Some(ImageFormat::Png),
+        image::ImageFormat::Jpeg => Some(ImageFormat::Jpeg),
+        image::ImageFormat::WebP => Some(ImageFormat::Webp),
+        image::ImageFormat::Gif => Some(ImageFormat::Gif),
+        image::ImageFormat::Bmp => Some(ImageFormat::Bmp),
+        image::ImageFormat::Tiff => Some(ImageFormat::Tiff),
+        _ => None,
+    };
+    let image = match drawable {
+        Some(drawable) => Image::from_bytes(drawable, bytes.to_vec()),
+        None => {
+            let decoded = DynamicImage::from_decoder(decoder).map_err(|e| e.to_string())?;
+            let mut png = Vec::new();
+            decoded
+                .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+                .map_err(|e| e.to_string())?;
+            Image::from_bytes(ImageFormat::Png, png)
+        }
+    };
+
+    Ok(DecodedImage {
+        image: Arc::new(image),
+        info: ImageInfo {
+            format: format!("{:?}", format).to_uppercase().into(),
+            width,
+            height,
+            color: color.into(),
+            exif,
+        },
+    })
+}
+
+fn decode_svg(bytes: &[u8]) -> Result<DecodedImage, String> {
+    let tree = usvg::Tree::from_data(bytes, &usvg::Options::default()).map_err(|e| e.to_string())?;
+    let size = tree.size();
+    Ok(DecodedImage {
+        image: Arc::new(Image::from_bytes(ImageFormat::Svg, bytes.to_vec())),
+        info: ImageInfo {
+            format: "SVG".into(),
+            width: size.width().round() as u32,
+            height: size.height().round() as u32,
+            color: "Vector".into(),
+            exif: Vec::new(),
+        },
+    })
+}
+
+/// Tags of the main image, with units, in file order.
+fn exif_fields(raw: Vec<u8>) -> Vec<(SharedString, SharedString)> {
+    let Ok(exif) = exif::Reader::new().read_raw(raw) else {
+        return Vec::new();
+    };
+    exif.fields()
+        .filter(|field| field.ifd_num == exif::In::PRIMARY)
+        .filter_map(|field| {
+            let value = field.display_value().with_unit(&exif).to_string();
+            (value.chars().count() <= MAX_EXIF_VALUE_CHARS)
+                .then(|| (field.tag.to_string().into(), value.into()))
+        })
+        .collect()
+}
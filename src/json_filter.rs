@@ -0,0 +1,148 @@
+use gpui::SharedString;
+use serde_json::Value;
+use serde_json_path::JsonPath;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Matches of a filter, rendered as pretty-printed JSON.
+pub struct FilterOutput {
+    pub matches: usize,
+    pub text: SharedString,
+}
+
+/// Evaluates a JSONPath (`$.items[*].id`) or jq-style path (`.items[].id`)
+/// against `value`. A single match is shown on its own, several as an array.
+pub fn apply(value: &Value, expression: &str) -> Result<FilterOutput, String> {
+    let path = JsonPath::parse(&to_json_path(expression.trim())).map_err(|e| e.to_string())?;
+    let matches = path.query(value).all();
+    let text = match matches.as_slice() {
+        [single] => serde_json::to_string_pretty(single),
+        all => serde_json::to_string_pretty(all),
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(FilterOutput {
+        matches: matches.len(),
+        text: text.into(),
+    })
+}
+
+/// Translates the jq path subset (`.`, `.a.b`, `.a[0]`, `.a[]`, `.["k"]`) to
+/// JSONPath; JSONPath expressions are returned unchanged. Quoted member names
+/// are copied as they are.
+fn to_json_path(expression: &str) -> String {
+    if !expression.starts_with('.') {
+        return expression.to_string();
+    }
+    let mut path = String::from("$");
+    let mut quote = None;
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(open) => {
+                path.push(c);
+                if c == '\\' {
+                    path.extend(chars.next());
+                } else if c == open {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' => {
+                    quote = Some(c);
+                    path.push(c);
+                }
+                // `.[` and a bare `.` only separate segments
+                '.' if matches!(chars.peek(), Some('[') | None) => {}
+                '[' if chars.peek() == Some(&']') => {
+                    chars.next();
+                    path.push_str("[*]");
+                }
+                _ => path.push(c),
+            },
+        }
+    }
+    path
+}
+
+/// Last filter used per request, persisted as JSON in the user's data directory.
+pub struct FilterStore {
+    filters: HashMap<String, String>,
+    path: Option<PathBuf>,
+}
+
+impl FilterStore {
+    pub fn load() -> Self {
+        let path = dirs::data_dir().map(|dir| dir.join("resty").join("json_filters.json"));
+        let filters = path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self { filters, path }
+    }
+
+    pub fn get(&self, request: &str) -> Option<&str> {
+        self.filters.get(request).map(String::as_str)
+    }
+
+    /// Remembers `filter` for `request`; an empty filter forgets it.
+    pub fn set(&mut self, request: &str, filter: &str) {
+        let changed = if filter.trim().is_empty() {
+            self.filters.remove(request).is_some()
+        } else {
+            self.filters.insert(request.to_string(), filter.to_string()).as_deref() != Some(filter)
+        };
+        if changed {
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let Ok(json) = serde_json::to_vec_pretty(&self.filters) else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(path, json);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn translates_jq_paths() {
+        assert_eq!(to_json_path("."), "$");
+        assert_eq!(to_json_path(".a.b"), "$.a.b");
+        assert_eq!(to_json_path(".a[0]"), "$.a[0]");
+        assert_eq!(to_json_path(".a[].id"), "$.a[*].id");
+        assert_eq!(to_json_path(".a.[0]"), "$.a[0]");
+        assert_eq!(to_json_path(r#".["k"]"#), r#"$["k"]"#);
+    }
+
+    #[test]
+    fn leaves_json_paths_unchanged() {
+        assert_eq!(to_json_path("$.a[*]"), "$.a[*]");
+    }
+
+    #[test]
+    fn copies_quoted_names_verbatim() {
+        assert_eq!(to_json_path(r#".["a.b[]"]"#), r#"$["a.b[]"]"#);
+        assert_eq!(to_json_path(".['x.']"), "$['x.']");
+        assert_eq!(to_json_path(r#".["a\"]."].b"#), r#"$["a\"]."].b"#);
+    }
+
+    #[test]
+    fn applies_jq_paths() {
+        let value = json!({ "items": [{ "id": 1 }, { "id": 2 }], "a.b": true });
+        let output = apply(&value, ".items[].id").unwrap();
+        assert_eq!(output.matches, 2);
+        assert_eq!(apply(&value, r#".["a.b"]"#).unwrap().text, "true");
+    }
+}
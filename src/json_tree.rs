@@ -0,0 +1,167 @@
+use gpui::SharedString;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Longest string value shown inline in a tree row.
+const MAX_INLINE_CHARS: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonKind {
+    Object,
+    Array,
+    String,
+    Number,
+    Bool,
+    Null,
+}
+
+/// One visible line of the tree.
+#[derive(Clone, Debug)]
+pub struct JsonRow {
+    /// JSON Pointer of the node, used to look it up and to track expansion.
+    pub pointer: String,
+    /// JSONPath of the node, e.g. `$.items[0].name`.
+    pub path: SharedString,
+    pub depth: usize,
+    pub key: Option<SharedString>,
+    pub kind: JsonKind,
+    pub summary: SharedString,
+    pub expanded: bool,
+}
+
+impl JsonRow {
+    pub fn expandable(&self) -> bool {
+        matches!(self.kind, JsonKind::Object | JsonKind::Array)
+    }
+}
+
+/// Flattens the expanded part of `value` into rows, depth first.
+pub fn visible_rows(value: &Value, expanded: &HashSet<String>) -> Vec<JsonRow> {
+    let mut rows = Vec::new();
+    push_rows(value, None, String::new(), "$".to_string(), 0, expanded, &mut rows);
+    rows
+}
+
+fn push_rows(
+    value: &Value,
+    key: Option<SharedString>,
+    pointer: String,
+    path: String,
+    depth: usize,
+    expanded: &HashSet<String>,
+    rows: &mut Vec<JsonRow>,
+) {
+    let is_expanded = expanded.contains(&pointer);
+    let (kind, summary) = summarize(value);
+    rows.push(JsonRow {
+        pointer: pointer.clone(),
+        path: path.clone().into(),
+        depth,
+        key,
+        kind,
+        summary,
+        expanded: is_expanded,
+    });
+    if !is_expanded {
+        return;
+    }
+
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map {
+                push_rows(
+                    child,
+                    Some(name.clone().into()),
+                    format!("{}/{}", pointer, escape_pointer(name)),
+                    format!("{}{}", path, path_segment(name)),
+                    depth + 1,
+                    expanded,
+                    rows,
+                );
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                push_rows(
+                    child,
+                    Some(i.to_string().into()),
+                    format!("{}/{}", pointer, i),
+                    format!("{}[{}]", path, i),
+                    depth + 1,
+                    expanded,
+                    rows,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Pointers of every object and array in `value`, for "expand all".
+pub fn container_pointers(value: &Value) -> HashSet<String> {
+    fn collect(value: &Value, pointer: String, out: &mut HashSet<String>) {
+        match value {
+            Value::Object(map) => {
+                for (name, child) in map {
+                    collect(child, format!("{}/{}", pointer, escape_pointer(name)), out);
+                }
+            }
+            Value::Array(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    collect(child, format!("{}/{}", pointer, i), out);
+                }
+            }
+            _ => return,
+        }
+        out.insert(pointer);
+    }
+
+    let mut pointers = HashSet::new();
+    collect(value, String::new(), &mut pointers);
+    pointers
+}
+
+fn summarize(value: &Value) -> (JsonKind, SharedString) {
+    match value {
+        Value::Object(map) => (
+            JsonKind::Object,
+            format!("{{{} {}}}", map.len(), plural(map.len(), "key", "keys")).into(),
+        ),
+        Value::Array(items) => (
+            JsonKind::Array,
+            format!("[{} {}]", items.len(), plural(items.len(), "item", "items")).into(),
+        ),
+        Value::String(text) => {
+            let mut shown: String = text.chars().take(MAX_INLINE_CHARS).collect();
+            if shown.len() < text.len() {
+                shown.push('…');
+            }
+            (JsonKind::String, Value::String(shown).to_string().into())
+        }
+        Value::Number(number) => (JsonKind::Number, number.to_string().into()),
+        Value::Bool(flag) => (JsonKind::Bool, flag.to_string().into()),
+        Value::Null => (JsonKind::Null, "null".into()),
+    }
+}
+
+fn plural(count: usize, one: &'static str, many: &'static str) -> &'static str {
+    if count == 1 { one } else { many }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// `.name` for identifier-like keys, `['...']` otherwise.
+pub fn path_segment(key: &str) -> String {
+    let identifier = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if identifier {
+        format!(".{}", key)
+    } else {
+        format!("['{}']", key.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
@@ -1,10 +1,33 @@
 #![windows_subsystem = "windows"]
 
 mod app_state;
+mod charset;
+mod cookies;
+mod decode;
+mod diff;
+mod download;
+mod grpc;
+mod headers;
+mod hex;
+mod highlight;
+mod image_info;
+mod json_filter;
+mod json_tree;
+mod markup;
+mod media;
+mod ndjson;
+mod pdf;
 mod response;
+mod search;
+mod snapshots;
+mod sse;
 mod text_input;
 mod text_area;
+mod text_lines;
 mod theme;
+mod timing;
+mod undo;
+mod websocket;
 mod workspace;
 
 use anyhow::Result;
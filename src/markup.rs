@@ -0,0 +1,446 @@
+use gpui::SharedString;
+
+/// HTML elements that never have a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source",
+    "track", "wbr",
+];
+
+/// HTML elements whose content is not markup.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea"];
+
+/// HTML elements whose whitespace is part of their content.
+const PRESERVE_WHITESPACE_ELEMENTS: &[&str] = &["pre", "textarea", "script", "style"];
+
+/// Elements that start a new block in the preview.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "dd", "div", "dl", "dt", "fieldset",
+    "figcaption", "figure", "footer", "form", "header", "main", "nav", "ol", "p", "section",
+    "table", "tbody", "thead", "tfoot", "tr", "ul",
+];
+
+/// Elements whose content is left out of the preview.
+const HIDDEN_ELEMENTS: &[&str] = &["head", "script", "style", "noscript", "template", "svg"];
+
+#[derive(Debug)]
+enum Node<'a> {
+    Open {
+        name: String,
+        raw: &'a str,
+        self_closing: bool,
+    },
+    Close {
+        name: String,
+        raw: &'a str,
+    },
+    Text(&'a str),
+    /// Comments, doctypes, processing instructions and CDATA sections.
+    Other(&'a str),
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches(['<', '/'])
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+/// End of the tag starting at `start`, past the `>`, skipping quoted values.
+fn tag_end(text: &str, start: usize) -> usize {
+    let mut quote = None;
+    for (i, c) in text[start..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return start + i + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// End of `delimiter` searched from `from`, or the end of the text.
+fn find_end(text: &str, from: usize, delimiter: &str) -> usize {
+    text[from..]
+        .find(delimiter)
+        .map_or(text.len(), |n| from + n + delimiter.len())
+}
+
+fn lex(text: &str, html: bool) -> Vec<Node<'_>> {
+    let bytes = text.as_bytes();
+    let mut nodes = Vec::new();
+    let mut i = 0;
+
+    while i < text.len() {
+        let rest = &text[i..];
+        if !rest.starts_with('<') {
+            let end = rest.find('<').map_or(text.len(), |n| i + n);
+            nodes.push(Node::Text(&text[i..end]));
+            i = end;
+            continue;
+        }
+
+        let end = if rest.starts_with("<!--") {
+            find_end(text, i + 4, "-->")
+        } else if rest.starts_with("<![CDATA[") {
+            find_end(text, i, "]]>")
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            tag_end(text, i)
+        } else if rest.starts_with("</") || bytes.get(i + 1).is_some_and(u8::is_ascii_alphabetic) {
+            let end = tag_end(text, i);
+            let raw = &text[i..end];
+            let name = tag_name(raw);
+            if raw.starts_with("</") {
+                nodes.push(Node::Close { name, raw });
+                i = end;
+                continue;
+            }
+
+            let self_closing = raw.ends_with("/>");
+            let raw_text = html && !self_closing && RAW_TEXT_ELEMENTS.contains(&name.as_str());
+            nodes.push(Node::Open {
+                name: name.clone(),
+                raw,
+                self_closing,
+            });
+            i = end;
+
+            // Keep the content of script and style elements untouched
+            if raw_text {
+                let close = format!("</{}", name);
+                let content_end = bytes[i..]
+                    .windows(close.len())
+                    .position(|window| window.eq_ignore_ascii_case(close.as_bytes()))
+                    .map_or(text.len(), |n| i + n);
+                if content_end > i {
+                    nodes.push(Node::Text(&text[i..content_end]));
+                }
+                i = content_end;
+            }
+            continue;
+        } else {
+            // A stray `<` is plain text
+            let end = rest[1..].find('<').map_or(text.len(), |n| i + 1 + n);
+            nodes.push(Node::Text(&text[i..end]));
+            i = end;
+            continue;
+        };
+        nodes.push(Node::Other(&text[i..end]));
+        i = end;
+    }
+    nodes
+}
+
+/// Byte offset of `part`, a slice of `text`, within `text`.
+fn offset_in(text: &str, part: &str) -> usize {
+    part.as_ptr() as usize - text.as_ptr() as usize
+}
+
+fn preserves_whitespace(name: &str, raw: &str, html: bool) -> bool {
+    (html && PRESERVE_WHITESPACE_ELEMENTS.contains(&name))
+        || raw.contains("xml:space=\"preserve\"")
+        || raw.contains("xml:space='preserve'")
+}
+
+/// Node index and byte end of the tag closing the element opened at `open`.
+fn matching_close(text: &str, nodes: &[Node], open: usize, name: &str) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    for (i, node) in nodes.iter().enumerate().skip(open + 1) {
+        match node {
+            Node::Open {
+                name: other,
+                self_closing: false,
+                ..
+            } if other == name => depth += 1,
+            Node::Close { name: other, raw } if other == name => {
+                if depth == 0 {
+                    return Some((i, offset_in(text, raw) + raw.len()));
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Re-indents an XML or HTML document, one element per line. Elements that
+/// only wrap a short text stay on one line; `<pre>`, `<textarea>`, scripts,
+/// styles and `xml:space="preserve"` elements are copied verbatim.
+pub fn pretty_print(text: &str, html: bool) -> String {
+    let nodes = lex(text, html);
+    let mut out = String::with_capacity(text.len() + text.len() / 4);
+    let mut depth: usize = 0;
+    let mut line = |depth: usize, content: &str| {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(content);
+    };
+
+    let mut i = 0;
+    while i < nodes.len() {
+        match &nodes[i] {
+            Node::Open {
+                name,
+                raw,
+                self_closing,
+            } => {
+                if !*self_closing && preserves_whitespace(name, raw, html) {
+                    let (last, end) = matching_close(text, &nodes, i, name)
+                        .unwrap_or((nodes.len() - 1, text.len()));
+                    line(depth, &text[offset_in(text, raw)..end]);
+                    i = last + 1;
+                    continue;
+                }
+                let void = html && VOID_ELEMENTS.contains(&name.as_str());
+                match (&nodes.get(i + 1), &nodes.get(i + 2)) {
+                    _ if *self_closing || void => line(depth, raw),
+                    (Some(Node::Close { name: close, raw: close_raw }), _) if close == name => {
+                        line(depth, &format!("{}{}", raw, close_raw));
+                        i += 1;
+                    }
+                    (
+                        Some(Node::Text(inner)),
+                        Some(Node::Close { name: close, raw: close_raw }),
+                    ) if close == name && !inner.trim().contains('\n') => {
+                        line(depth, &format!("{}{}{}", raw, inner.trim(), close_raw));
+                        i += 2;
+                    }
+                    _ => {
+                        line(depth, raw);
+                        depth += 1;
+                    }
+                }
+            }
+            Node::Close { raw, .. } => {
+                depth = depth.saturating_sub(1);
+                line(depth, raw);
+            }
+            Node::Text(text) => {
+                for text_line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                    line(depth, text_line);
+                }
+            }
+            Node::Other(raw) => line(depth, raw.trim()),
+        }
+        i += 1;
+    }
+    out
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockKind {
+    Title,
+    Heading(u8),
+    Paragraph,
+    /// A list item and its nesting depth.
+    ListItem(usize),
+    Code,
+    Rule,
+}
+
+/// One block of the simplified HTML preview.
+#[derive(Clone, Debug)]
+pub struct PreviewBlock {
+    pub kind: BlockKind,
+    pub text: SharedString,
+}
+
+/// Reduces an HTML document to its title, headings, paragraphs, list items
+/// and preformatted blocks, dropping scripts, styles and the rest of `<head>`.
+pub fn html_preview(text: &str) -> Vec<PreviewBlock> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    let mut kind = BlockKind::Paragraph;
+    let mut hidden = 0usize;
+    let mut in_title = false;
+    let mut list_depth = 0usize;
+
+    fn flush(blocks: &mut Vec<PreviewBlock>, current: &mut String, kind: BlockKind) {
+        let text = if kind == BlockKind::Code {
+            current.trim_matches('\n').to_string()
+        } else {
+            current.split_whitespace().collect::<Vec<_>>().join(" ")
+        };
+        current.clear();
+        if !text.is_empty() {
+            blocks.push(PreviewBlock {
+                kind,
+                text: text.into(),
+            });
+        }
+    }
+
+    for node in lex(text, true) {
+        match node {
+            Node::Open {
+                name,
+                raw,
+                self_closing,
+            } => {
+                let name = name.as_str();
+                if name == "title" {
+                    in_title = true;
+                    continue;
+                }
+                if HIDDEN_ELEMENTS.contains(&name) {
+                    if !self_closing {
+                        hidden += 1;
+                    }
+                    continue;
+                }
+                if hidden > 0 {
+                    continue;
+                }
+                match name {
+                    "br" => current.push('\n'),
+                    "hr" => {
+                        flush(&mut blocks, &mut current, kind);
+                        blocks.push(PreviewBlock {
+                            kind: BlockKind::Rule,
+                            text: SharedString::default(),
+                        });
+                    }
+                    "img" => {
+                        if let Some(alt) = attribute(raw, "alt").filter(|alt| !alt.is_empty()) {
+                            current.push_str(&format!(" [{}] ", alt));
+                        }
+                    }
+                    "td" | "th" if !current.trim().is_empty() => current.push_str(" | "),
+                    "ul" | "ol" => {
+                        flush(&mut blocks, &mut current, kind);
+                        list_depth += 1;
+                    }
+                    _ => {
+                        let next = match name {
+                            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                                BlockKind::Heading(name.as_bytes()[1] - b'0')
+                            }
+                            "li" => BlockKind::ListItem(list_depth.saturating_sub(1)),
+                            "pre" => BlockKind::Code,
+                            _ if BLOCK_ELEMENTS.contains(&name) => BlockKind::Paragraph,
+                            _ => continue,
+                        };
+                        flush(&mut blocks, &mut current, kind);
+                        kind = next;
+                    }
+                }
+            }
+            Node::Close { name, .. } => {
+                let name = name.as_str();
+                if name == "title" {
+                    in_title = false;
+                    let title = decode_entities(current.trim());
+                    current.clear();
+                    if !title.is_empty() {
+                        blocks.push(PreviewBlock {
+                            kind: BlockKind::Title,
+                            text: title.into(),
+                        });
+                    }
+                    continue;
+                }
+                if HIDDEN_ELEMENTS.contains(&name) {
+                    hidden = hidden.saturating_sub(1);
+                    continue;
+                }
+                if hidden > 0 {
+                    continue;
+                }
+                if name == "ul" || name == "ol" {
+                    list_depth = list_depth.saturating_sub(1);
+                }
+                let block = matches!(name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "li" | "pre")
+                    || name == "ul"
+                    || name == "ol"
+                    || BLOCK_ELEMENTS.contains(&name);
+                if block {
+                    flush(&mut blocks, &mut current, kind);
+                    kind = BlockKind::Paragraph;
+                }
+            }
+            Node::Text(text) if hidden == 0 || in_title => current.push_str(&decode_entities(text)),
+            Node::Text(_) | Node::Other(_) => {}
+        }
+    }
+    flush(&mut blocks, &mut current, kind);
+    blocks
+}
+
+/// Value of the attribute `name` in an opening tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(n) = lower[from..].find(name) {
+        let start = from + n;
+        from = start + name.len();
+        let preceded = lower[..start].ends_with(|c: char| c.is_whitespace());
+        let rest = tag[from..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value = rest[1..].trim_start();
+        let (quote, value) = match value.chars().next() {
+            Some(q @ ('"' | '\'')) => (Some(q), &value[1..]),
+            _ => (None, value),
+        };
+        let end = match quote {
+            Some(q) => value.find(q),
+            None => value.find(|c: char| c.is_whitespace() || c == '>'),
+        };
+        return Some(decode_entities(&value[..end.unwrap_or(value.len())]));
+    }
+    None
+}
+
+/// Decodes the named entities common in error pages and numeric references.
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest[1..].find(';').filter(|&n| n <= 10).and_then(|n| {
+            let entity = &rest[1..n + 1];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                "copy" => Some('©'),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "hellip" => Some('…'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, n + 2))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,491 @@
+use gpui::SharedString;
+
+/// Container, duration and codec details read from the headers of an audio
+/// or video file, without decoding it.
+#[derive(Clone, Debug, Default)]
+pub struct MediaInfo {
+    pub container: &'static str,
+    /// Seconds.
+    pub duration: Option<f64>,
+    pub fields: Vec<(SharedString, SharedString)>,
+}
+
+impl MediaInfo {
+    fn field(&mut self, label: &'static str, value: impl Into<SharedString>) {
+        self.fields.push((label.into(), value.into()));
+    }
+}
+
+/// `complete` is false when `bytes` is only the start of the body, which
+/// rules out estimates that depend on the full length.
+pub fn info(bytes: &[u8], complete: bool) -> MediaInfo {
+    let mut info = MediaInfo {
+        container: "Unknown",
+        ..Default::default()
+    };
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+        wav(bytes, &mut info);
+    } else if bytes.starts_with(b"fLaC") {
+        flac(bytes, &mut info);
+    } else if bytes.starts_with(b"OggS") {
+        ogg(bytes, complete, &mut info);
+    } else if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        mp4(bytes, &mut info);
+    } else if bytes.starts_with(b"\x1a\x45\xdf\xa3") {
+        matroska(bytes, &mut info);
+    } else if bytes.starts_with(b"ID3") || mp3_frame_start(bytes, 0).is_some() {
+        mp3(bytes, complete, &mut info);
+    }
+    info
+}
+
+/// `1:02:03` or `2:03.5`.
+pub fn format_clock(seconds: f64) -> String {
+    let whole = seconds as u64;
+    let (hours, minutes) = (whole / 3600, whole % 3600 / 60);
+    let secs = seconds - (hours * 3600 + minutes * 60) as f64;
+    if hours > 0 {
+        format!("{}:{:02}:{:04.1}", hours, minutes, secs)
+    } else {
+        format!("{}:{:04.1}", minutes, secs)
+    }
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u16_be(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u64_be(bytes: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(bytes.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn channels_label(channels: u16) -> String {
+    match channels {
+        1 => "1 (mono)".to_string(),
+        2 => "2 (stereo)".to_string(),
+        n => n.to_string(),
+    }
+}
+
+fn wav(bytes: &[u8], info: &mut MediaInfo) {
+    info.container = "WAV";
+    let mut pos = 12;
+    let mut byte_rate = 0;
+    while let (Some(id), Some(size)) = (bytes.get(pos..pos + 4), u32_le(bytes, pos + 4)) {
+        let data = pos + 8;
+        match id {
+            b"fmt " => {
+                let codec = match u16_le(bytes, data).unwrap_or(0) {
+                    1 => "PCM",
+                    3 => "IEEE float",
+                    6 => "A-law",
+                    7 => "µ-law",
+                    0x55 => "MP3",
+                    0xfffe => "PCM (extensible)",
+                    _ => "Other",
+                };
+                info.field("Codec", codec);
+                if let Some(channels) = u16_le(bytes, data + 2) {
+                    info.field("Channels", channels_label(channels));
+                }
+                if let Some(rate) = u32_le(bytes, data + 4) {
+                    info.field("Sample rate", format!("{} Hz", rate));
+                }
+                byte_rate = u32_le(bytes, data + 8).unwrap_or(0);
+                if let Some(bits) = u16_le(bytes, data + 14) {
+                    info.field("Bit depth", format!("{} bit", bits));
+                }
+            }
+            b"data" if byte_rate > 0 => {
+                info.duration = Some(f64::from(size) / f64::from(byte_rate));
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size
+        pos = data + size as usize + (size as usize & 1);
+    }
+}
+
+fn flac(bytes: &[u8], info: &mut MediaInfo) {
+    info.container = "FLAC";
+    info.field("Codec", "FLAC");
+    // The first metadata block is always STREAMINFO
+    let Some(si) = bytes.get(8..26) else {
+        return;
+    };
+    let rate = (u32::from(si[10]) << 12) | (u32::from(si[11]) << 4) | (u32::from(si[12]) >> 4);
+    let channels = u16::from((si[12] >> 1) & 7) + 1;
+    let bits = (((si[12] & 1) << 4) | (si[13] >> 4)) + 1;
+    let samples = (u64::from(si[13] & 0x0f) << 32)
+        | u64::from(u32::from_be_bytes([si[14], si[15], si[16], si[17]]));
+    info.field("Sample rate", format!("{} Hz", rate));
+    info.field("Channels", channels_label(channels));
+    info.field("Bit depth", format!("{} bit", bits));
+    if rate > 0 && samples > 0 {
+        info.duration = Some(samples as f64 / f64::from(rate));
+    }
+}
+
+fn ogg(bytes: &[u8], complete: bool, info: &mut MediaInfo) {
+    info.container = "Ogg";
+    let Some(&segments) = bytes.get(26) else {
+        return;
+    };
+    let packet = &bytes[(27 + segments as usize).min(bytes.len())..];
+    // Granule positions count samples at 48 kHz for Opus
+    let (rate, pre_skip) = if packet.starts_with(b"OpusHead") {
+        info.field("Codec", "Opus");
+        if let Some(&channels) = packet.get(9) {
+            info.field("Channels", channels_label(channels.into()));
+        }
+        if let Some(rate) = u32_le(packet, 12).filter(|&rate| rate > 0) {
+            info.field("Input sample rate", format!("{} Hz", rate));
+        }
+        (48_000, u16_le(packet, 10).unwrap_or(0))
+    } else if packet.starts_with(b"\x01vorbis") {
+        info.field("Codec", "Vorbis");
+        if let Some(&channels) = packet.get(11) {
+            info.field("Channels", channels_label(channels.into()));
+        }
+        let rate = u32_le(packet, 12).unwrap_or(0);
+        info.field("Sample rate", format!("{} Hz", rate));
+        (rate, 0)
+    } else if packet.starts_with(b"\x80theora") {
+        info.field("Codec", "Theora");
+        return;
+    } else {
+        return;
+    };
+
+    if !complete {
+        return;
+    }
+    let last_page = bytes.windows(4).rposition(|window| window == b"OggS");
+    let granule = last_page
+        .and_then(|at| bytes.get(at + 6..at + 14))
+        .map(|raw| u64::from_le_bytes(raw.try_into().unwrap_or_default()));
+    if let Some(granule) = granule.filter(|_| rate > 0) {
+        info.duration = Some(granule.saturating_sub(pre_skip.into()) as f64 / f64::from(rate));
+    }
+}
+
+/// Offset of the first MPEG audio frame at or after `from`.
+fn mp3_frame_start(bytes: &[u8], from: usize) -> Option<usize> {
+    let header = bytes.get(from..from + 4)?;
+    (header[0] == 0xff && header[1] & 0xe0 == 0xe0 && (header[1] >> 1) & 3 != 0 && header[2] >> 4 != 0xf)
+        .then_some(from)
+}
+
+fn mp3(bytes: &[u8], complete: bool, info: &mut MediaInfo) {
+    info.container = "MPEG audio";
+    let mut start = 0;
+    if bytes.starts_with(b"ID3") && bytes.len() >= 10 {
+        let size = bytes[6..10]
+            .iter()
+            .fold(0usize, |size, &b| (size << 7) | usize::from(b & 0x7f));
+        let footer = if bytes[5] & 0x10 != 0 { 10 } else { 0 };
+        start = 10 + size + footer;
+    }
+    let Some(frame) = (start..bytes.len().min(start + 4096)).find_map(|at| mp3_frame_start(bytes, at))
+    else {
+        return;
+    };
+    let header = &bytes[frame..frame + 4];
+    let version = (header[1] >> 3) & 3;
+    let layer = (header[1] >> 1) & 3;
+    if layer != 1 {
+        info.field("Codec", if layer == 2 { "MP2" } else { "MP1" });
+        return;
+    }
+    info.field("Codec", "MP3");
+
+    const MPEG1: [u32; 15] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+    const MPEG2: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+    let bitrate = if version == 3 { MPEG1 } else { MPEG2 }[usize::from(header[2] >> 4)];
+    let rate = match ((header[2] >> 2) & 3, version) {
+        (index @ 0..=2, 3) => [44_100, 48_000, 32_000][usize::from(index)],
+        (index @ 0..=2, 2) => [22_050, 24_000, 16_000][usize::from(index)],
+        (index @ 0..=2, _) => [11_025, 12_000, 8_000][usize::from(index)],
+        _ => 0,
+    };
+    let channels = if header[3] >> 6 == 3 { 1 } else { 2 };
+    info.field("Sample rate", format!("{} Hz", rate));
+    info.field("Channels", channels_label(channels));
+
+    // A Xing/Info header in the first frame gives the exact frame count of VBR files
+    let samples_per_frame = if version == 3 { 1152 } else { 576 };
+    let xing = bytes
+        .get(frame..bytes.len().min(frame + 64))
+        .and_then(|head| head.windows(4).position(|w| w == b"Xing" || w == b"Info"))
+        .map(|at| frame + at);
+    let frames = xing
+        .filter(|&at| u32_be(bytes, at + 4).is_some_and(|flags| flags & 1 != 0))
+        .and_then(|at| u32_be(bytes, at + 8));
+    match frames {
+        Some(frames) if rate > 0 => {
+            info.field("Bitrate", "variable");
+            info.duration = Some(f64::from(frames) * f64::from(samples_per_frame) / f64::from(rate));
+        }
+        _ if bitrate > 0 => {
+            info.field("Bitrate", format!("{} kbps", bitrate));
+            if complete {
+                info.duration = Some((bytes.len() - frame) as f64 * 8.0 / f64::from(bitrate * 1000));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Boxes directly inside `bytes[range]` as `(type, body range)`.
+fn mp4_boxes(bytes: &[u8], start: usize, end: usize) -> Vec<([u8; 4], usize, usize)> {
+    let mut boxes = Vec::new();
+    let mut pos = start;
+    while pos + 8 <= end {
+        let Some(size) = u32_be(bytes, pos) else {
+            break;
+        };
+        let kind: [u8; 4] = bytes[pos + 4..pos + 8].try_into().unwrap_or_default();
+        let (header, size) = match size {
+            0 => (8, end - pos),
+            1 => match u64_be(bytes, pos + 8) {
+                Some(size) => (16, usize::try_from(size).unwrap_or(usize::MAX)),
+                None => break,
+            },
+            size => (8, size as usize),
+        };
+        if size < header || pos + header > end {
+            break;
+        }
+        // Sizes come from the body and may claim more than is there
+        let box_end = pos.saturating_add(size).min(end);
+        boxes.push((kind, pos + header, box_end));
+        pos = box_end;
+    }
+    boxes
+}
+
+fn mp4_child(bytes: &[u8], start: usize, end: usize, kind: &[u8; 4]) -> Option<(usize, usize)> {
+    mp4_boxes(bytes, start, end)
+        .into_iter()
+        .find(|(k, _, _)| k == kind)
+        .map(|(_, start, end)| (start, end))
+}
+
+fn mp4(bytes: &[u8], info: &mut MediaInfo) {
+    info.container = "MP4";
+    if let Ok(brand) = std::str::from_utf8(&bytes[8..12]) {
+        if brand == "qt  " {
+            info.container = "QuickTime";
+        }
+        info.field("Brand", brand.trim().to_string());
+    }
+    let Some((moov, moov_end)) = mp4_child(bytes, 0, bytes.len(), b"moov") else {
+        return;
+    };
+
+    // An empty `mvhd` at the end of the body has no version byte to read
+    if let Some((mvhd, _)) = mp4_child(bytes, moov, moov_end, b"mvhd")
+        && let Some(&version) = bytes.get(mvhd)
+    {
+        let (timescale, duration) = if version == 1 {
+            (u32_be(bytes, mvhd + 20), u64_be(bytes, mvhd + 24))
+        } else {
+            (u32_be(bytes, mvhd + 12), u32_be(bytes, mvhd + 16).map(u64::from))
+        };
+        if let (Some(timescale), Some(duration)) = (timescale.filter(|&t| t > 0), duration) {
+            info.duration = Some(duration as f64 / f64::from(timescale));
+        }
+    }
+
+    for (kind, trak, trak_end) in mp4_boxes(bytes, moov, moov_end) {
+        if &kind != b"trak" {
+            continue;
+        }
+        let Some((mdia, mdia_end)) = mp4_child(bytes, trak, trak_end, b"mdia") else {
+            continue;
+        };
+        let handler = mp4_child(bytes, mdia, mdia_end, b"hdlr")
+            .and_then(|(hdlr, _)| bytes.get(hdlr + 8..hdlr + 12));
+        let entry = mp4_child(bytes, mdia, mdia_end, b"minf")
+            .and_then(|(minf, end)| mp4_child(bytes, minf, end, b"stbl"))
+            .and_then(|(stbl, end)| mp4_child(bytes, stbl, end, b"stsd"))
+            .map(|(stsd, _)| stsd + 8);
+        let Some(entry) = entry else {
+            continue;
+        };
+        let codec = bytes
+            .get(entry + 4..entry + 8)
+            .map(|fourcc| String::from_utf8_lossy(fourcc).trim().to_string())
+            .unwrap_or_default();
+        match handler {
+            Some(b"vide") => {
+                let size = u16_be(bytes, entry + 32).zip(u16_be(bytes, entry + 34));
+                let value = match size {
+                    Some((width, height)) => format!("{} · {}×{}", codec, width, height),
+                    None => codec,
+                };
+                info.field("Video", value);
+            }
+            Some(b"soun") => {
+                let channels = u16_be(bytes, entry + 24).unwrap_or(0);
+                let rate = u32_be(bytes, entry + 32).unwrap_or(0) >> 16;
+                info.field("Audio", format!("{} · {} Hz · {} ch", codec, rate, channels));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads a few well-known elements by their IDs rather than walking the full
+/// EBML tree.
+fn matroska(bytes: &[u8], info: &mut MediaInfo) {
+    let find = |id: &[u8]| bytes.windows(id.len()).position(|window| window == id);
+    info.container = match find(b"webm") {
+        Some(_) => "WebM",
+        None => "Matroska",
+    };
+
+    // TimecodeScale, in nanoseconds per tick
+    let scale = find(&[0x2a, 0xd7, 0xb1])
+        .and_then(|at| {
+            let len = usize::from(bytes.get(at + 3)? & 0x0f);
+            let raw = bytes.get(at + 4..at + 4 + len)?;
+            Some(raw.iter().fold(0u64, |value, &b| (value << 8) | u64::from(b)))
+        })
+        .unwrap_or(1_000_000);
+    let duration = find(&[0x44, 0x89]).and_then(|at| match bytes.get(at + 2)? {
+        0x84 => Some(f64::from(f32::from_be_bytes(bytes.get(at + 3..at + 7)?.try_into().ok()?))),
+        0x88 => Some(f64::from_be_bytes(bytes.get(at + 3..at + 11)?.try_into().ok()?)),
+        _ => None,
+    });
+    info.duration = duration.map(|ticks| ticks * scale as f64 / 1e9);
+
+    // CodecID strings look like `V_VP9` or `A_OPUS`
+    let mut pos = 0;
+    while let Some(at) = bytes[pos..].iter().position(|&b| b == 0x86).map(|at| pos + at) {
+        pos = at + 1;
+        let Some(&size) = bytes.get(at + 1).filter(|&&size| (0x82..0xa0).contains(&size)) else {
+            continue;
+        };
+        let Some(raw) = bytes.get(at + 2..at + 2 + usize::from(size & 0x7f)) else {
+            continue;
+        };
+        let Ok(codec) = std::str::from_utf8(raw) else {
+            continue;
+        };
+        let label = match codec.get(..2) {
+            Some("V_") => "Video",
+            Some("A_") => "Audio",
+            _ => continue,
+        };
+        if codec[2..].bytes().all(|b| b.is_ascii_alphanumeric() || b == b'/' || b == b'_') {
+            info.field(label, codec[2..].to_string());
+        }
+    }
+}
+
+/// File extension for saving a body of `mime_type`, so the system picks the
+/// right app to open it.
+pub fn extension(mime_type: &str) -> &str {
+    match mime_type {
+        "application/pdf" => "pdf",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/ogg" | "audio/opus" => "ogg",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/mp4" | "audio/x-m4a" => "m4a",
+        "audio/aac" => "aac",
+        "video/quicktime" => "mov",
+        "video/x-matroska" => "mkv",
+        "video/x-msvideo" => "avi",
+        _ => mime_type.rsplit('/').next().unwrap_or("bin"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn ftyp() -> Vec<u8> {
+        mp4_box(b"ftyp", b"isom")
+    }
+
+    #[test]
+    fn reads_mvhd_duration() {
+        let mut mvhd = vec![0u8; 20];
+        mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+        mvhd[16..20].copy_from_slice(&2500u32.to_be_bytes());
+        let mut bytes = ftyp();
+        bytes.extend(mp4_box(b"moov", &mp4_box(b"mvhd", &mvhd)));
+
+        let info = info(&bytes, true);
+        assert_eq!(info.container, "MP4");
+        assert_eq!(info.duration, Some(2.5));
+    }
+
+    #[test]
+    fn empty_mvhd_at_end_of_body() {
+        let mut bytes = ftyp();
+        bytes.extend(mp4_box(b"moov", &mp4_box(b"mvhd", &[])));
+        assert_eq!(bytes.len(), 28);
+
+        let info = info(&bytes, true);
+        assert_eq!(info.container, "MP4");
+        assert_eq!(info.duration, None);
+    }
+
+    #[test]
+    fn truncated_box_is_clipped_to_the_body() {
+        let mut bytes = 100u32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"moov");
+        bytes.extend_from_slice(&[0; 12]);
+
+        assert_eq!(mp4_boxes(&bytes, 0, bytes.len()), vec![(*b"moov", 8, 20)]);
+    }
+
+    #[test]
+    fn oversized_largesize_box_is_clipped_to_the_body() {
+        let mut bytes = 1u32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"mdat");
+        bytes.extend_from_slice(&u64::MAX.to_be_bytes());
+        bytes.extend_from_slice(&[0; 8]);
+
+        assert_eq!(mp4_boxes(&bytes, 0, bytes.len()), vec![(*b"mdat", 16, 24)]);
+    }
+
+    #[test]
+    fn box_header_past_the_range_is_dropped() {
+        let mut bytes = 1u32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"mdat");
+        bytes.extend_from_slice(&64u64.to_be_bytes());
+
+        assert!(mp4_boxes(&bytes, 0, 12).is_empty());
+    }
+
+    #[test]
+    fn box_smaller_than_its_header_stops_parsing() {
+        let mut bytes = 4u32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"free");
+
+        assert!(mp4_boxes(&bytes, 0, bytes.len()).is_empty());
+    }
+}
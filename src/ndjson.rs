@@ -0,0 +1,73 @@
+use gpui::SharedString;
+
+/// Content types whose bodies are newline-delimited JSON records.
+const NDJSON_TYPES: [&str; 4] = [
+    "application/x-ndjson",
+    "application/ndjson",
+    "application/jsonl",
+    "application/x-jsonlines",
+];
+
+pub fn is_ndjson(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or("").trim();
+    NDJSON_TYPES
+        .iter()
+        .any(|ty| mime.eq_ignore_ascii_case(ty))
+}
+
+/// One line of an NDJSON body, pretty-printed when it is valid JSON.
+#[derive(Clone, Debug)]
+pub struct NdJsonRecord {
+    pub text: SharedString,
+    pub valid: bool,
+}
+
+impl NdJsonRecord {
+    fn parse(line: &str) -> Self {
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(value) => Self {
+                text: serde_json::to_string_pretty(&value)
+                    .unwrap_or_else(|_| line.to_string())
+                    .into(),
+                valid: true,
+            },
+            Err(_) => Self {
+                text: line.to_string().into(),
+                valid: false,
+            },
+        }
+    }
+}
+
+/// Splits a body fed in arbitrary chunks into records, one per non-empty line.
+#[derive(Default)]
+pub struct NdJsonParser {
+    line: Vec<u8>,
+}
+
+impl NdJsonParser {
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<NdJsonRecord> {
+        let mut records = Vec::new();
+        for &byte in chunk {
+            if byte == b'\n' {
+                let line = std::mem::take(&mut self.line);
+                records.extend(Self::record(&line));
+            } else {
+                self.line.push(byte);
+            }
+        }
+        records
+    }
+
+    /// Flushes a final record that was not terminated by a newline.
+    pub fn finish(&mut self) -> Option<NdJsonRecord> {
+        let line = std::mem::take(&mut self.line);
+        Self::record(&line)
+    }
+
+    fn record(line: &[u8]) -> Option<NdJsonRecord> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        (!line.is_empty()).then(|| NdJsonRecord::parse(line))
+    }
+}
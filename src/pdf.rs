@@ -0,0 +1,155 @@
+use gpui::SharedString;
+
+/// Document facts read from the raw bytes of a PDF, without rendering it.
+#[derive(Clone, Debug, Default)]
+pub struct PdfInfo {
+    pub version: Option<SharedString>,
+    /// Counted from page objects, so it is missing when they are compressed
+    /// into object streams.
+    pub pages: Option<usize>,
+    pub encrypted: bool,
+    /// `/Title`, `/Author` and the like from the document information dictionary.
+    pub metadata: Vec<(SharedString, SharedString)>,
+}
+
+const INFO_KEYS: &[&str] = &[
+    "Title",
+    "Author",
+    "Subject",
+    "Keywords",
+    "Creator",
+    "Producer",
+    "CreationDate",
+    "ModDate",
+];
+
+pub fn info(bytes: &[u8]) -> PdfInfo {
+    let version = bytes
+        .strip_prefix(b"%PDF-")
+        .map(|rest| {
+            let end = rest
+                .iter()
+                .position(|b| !(b.is_ascii_digit() || *b == b'.'))
+                .unwrap_or(rest.len());
+            String::from_utf8_lossy(&rest[..end]).into_owned().into()
+        });
+    let pages = count_pages(bytes);
+    let metadata = INFO_KEYS
+        .iter()
+        .filter_map(|key| {
+            let value = literal_after(bytes, format!("/{}", key).as_bytes())?;
+            (!value.trim().is_empty()).then(|| ((*key).into(), value.into()))
+        })
+        .collect();
+
+    PdfInfo {
+        version,
+        pages: (pages > 0).then_some(pages),
+        encrypted: find(bytes, b"/Encrypt", 0).is_some(),
+        metadata,
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| from + i)
+}
+
+/// Counts `/Type /Page` objects, skipping `/Type /Pages` tree nodes.
+fn count_pages(bytes: &[u8]) -> usize {
+    let mut count = 0;
+    let mut pos = 0;
+    while let Some(at) = find(bytes, b"/Type", pos) {
+        pos = at + 5;
+        let rest = &bytes[pos..];
+        let skipped = rest.iter().take_while(|b| b.is_ascii_whitespace()).count();
+        let rest = &rest[skipped..];
+        if rest.starts_with(b"/Page") && !rest[5..].first().is_some_and(u8::is_ascii_alphanumeric) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// The literal `(string)` following `key`, with escapes resolved. Hex and
+/// UTF-16 strings are decoded too.
+fn literal_after(bytes: &[u8], key: &[u8]) -> Option<String> {
+    let mut pos = find(bytes, key, 0)? + key.len();
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    let raw = match bytes.get(pos)? {
+        b'(' => {
+            let mut raw = Vec::new();
+            let mut depth = 0;
+            let mut i = pos + 1;
+            while let Some(&b) = bytes.get(i) {
+                match b {
+                    b'\\' => {
+                        i += 1;
+                        match bytes.get(i)? {
+                            b'n' => raw.push(b'\n'),
+                            b'r' => raw.push(b'\r'),
+                            b't' => raw.push(b'\t'),
+                            digit @ b'0'..=b'7' => {
+                                let mut value = u32::from(digit - b'0');
+                                for _ in 0..2 {
+                                    match bytes.get(i + 1) {
+                                        Some(d @ b'0'..=b'7') => {
+                                            value = value * 8 + u32::from(d - b'0');
+                                            i += 1;
+                                        }
+                                        _ => break,
+                                    }
+                                }
+                                raw.push(value as u8);
+                            }
+                            other => raw.push(*other),
+                        }
+                    }
+                    b'(' => {
+                        depth += 1;
+                        raw.push(b);
+                    }
+                    b')' if depth == 0 => break,
+                    b')' => {
+                        depth -= 1;
+                        raw.push(b);
+                    }
+                    _ => raw.push(b),
+                }
+                i += 1;
+            }
+            raw
+        }
+        b'<' => {
+            let end = find(bytes, b">", pos)?;
+            let hex: Vec<u8> = bytes[pos + 1..end]
+                .iter()
+                .copied()
+                .filter(u8::is_ascii_hexdigit)
+                .collect();
+            hex.chunks(2)
+                .filter_map(|pair| {
+                    let text = std::str::from_utf8(pair).ok()?;
+                    u8::from_str_radix(&format!("{:0<2}", text), 16).ok()
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+
+    Some(match raw.strip_prefix(b"\xfe\xff") {
+        Some(utf16) => String::from_utf16_lossy(
+            &utf16
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        // PDFDocEncoding matches Latin-1 for printable characters
+        None => raw.iter().map(|&b| b as char).collect(),
+    })
+}
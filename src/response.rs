@@ -1,23 +1,119 @@
+use crate::charset::{self, EncodingSource, TextEncoding};
+use crate::cookies::StoredCookie;
+use crate::decode::BinaryFormat;
+use crate::headers;
+use crate::markup::{self, PreviewBlock};
+use crate::media::{self, MediaInfo};
+use crate::ndjson::NdJsonRecord;
+use crate::pdf::{self, PdfInfo};
+use crate::sse::SseEvent;
+use crate::timing::Timings;
 use gpui::*;
+use encoding_rs::Encoding;
 use serde_json::Value;
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempPath;
 
 #[derive(Clone, Debug)]
 pub enum ResponseContent {
     Text(SharedString),
-    Json(SharedString),
-    Image(Vec<u8>, SharedString), // bytes + mime type
-    Binary(Vec<u8>),
+    /// Pretty-printed text and the parsed document.
+    Json(SharedString, Arc<Value>),
+    /// Binary encoding decoded to JSON: the format, pretty text and tree.
+    Decoded(BinaryFormat, SharedString, Arc<Value>),
+    /// Pretty-printed XML document.
+    Xml(SharedString),
+    /// Pretty-printed HTML source and its simplified preview.
+    Html(SharedString, Arc<[PreviewBlock]>),
+    Image(Arc<[u8]>, SharedString), // bytes + mime type
+    /// Facts read from a PDF document.
+    Pdf(Arc<PdfInfo>),
+    /// Audio or video: the mime type and what its headers tell.
+    Media(SharedString, Arc<MediaInfo>),
+    Binary,
+    /// Events of a `text/event-stream` response, appended as they arrive.
+    EventStream(Vec<SseEvent>),
+    /// Records of a newline-delimited JSON body, appended as they arrive.
+    NdJson(Vec<NdJsonRecord>),
     Error(SharedString),
 }
 
+/// Raw response body. Large bodies are spooled to a temporary file that is
+/// removed once the last clone of the response is dropped.
+#[derive(Clone, Debug)]
+pub enum ResponseBody {
+    Memory(Arc<[u8]>),
+    Spooled {
+        file: Arc<TempPath>,
+        preview: Arc<[u8]>,
+    },
+}
+
+impl ResponseBody {
+    /// The whole body when kept in memory, otherwise its first bytes.
+    pub fn preview(&self) -> &Arc<[u8]> {
+        match self {
+            ResponseBody::Memory(bytes) => bytes,
+            ResponseBody::Spooled { preview, .. } => preview,
+        }
+    }
+
+    pub fn is_spooled(&self) -> bool {
+        matches!(self, ResponseBody::Spooled { .. })
+    }
+
+    /// Length of the whole body in bytes.
+    pub fn len(&self) -> std::io::Result<u64> {
+        match self {
+            ResponseBody::Memory(bytes) => Ok(bytes.len() as u64),
+            ResponseBody::Spooled { file, .. } => std::fs::metadata(file.as_ref()).map(|meta| meta.len()),
+        }
+    }
+
+    /// Reads the whole body from its start, including what was spooled to disk.
+    pub fn reader(&self) -> std::io::Result<Box<dyn Read + '_>> {
+        match self {
+            ResponseBody::Memory(bytes) => Ok(Box::new(&bytes[..])),
+            ResponseBody::Spooled { file, .. } => Ok(Box::new(std::fs::File::open(file.as_ref())?)),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        match self {
+            ResponseBody::Memory(bytes) => std::fs::write(path, bytes),
+            ResponseBody::Spooled { file, .. } => std::fs::copy(file.as_ref(), path).map(|_| ()),
+        }
+    }
+}
+
+/// A single intermediate response that was answered with a redirect.
+#[derive(Clone, Debug)]
+pub struct RedirectHop {
+    pub status: u16,
+    pub url: SharedString,
+    pub location: SharedString,
+    pub headers: Vec<(SharedString, SharedString)>,
+    pub elapsed_ms: u64,
+}
+
 #[derive(Clone, Debug)]
 pub struct Response {
     pub status: u16,
     pub status_text: SharedString,
     pub headers: Vec<(SharedString, SharedString)>,
     pub content: ResponseContent,
+    pub body: ResponseBody,
     pub elapsed_ms: u64,
     pub size_bytes: usize,
+    pub redirects: Vec<RedirectHop>,
+    pub timing: Timings,
+    /// Cookies set by the final response and by every redirect hop.
+    pub cookies: Vec<StoredCookie>,
+    /// Charset the body was decoded with, for text responses.
+    pub encoding: Option<TextEncoding>,
 }
 
 impl Response {
@@ -27,71 +123,178 @@ impl Response {
             status_text: "Error".into(),
             headers: vec![],
             content: ResponseContent::Error(error.into()),
+            body: ResponseBody::Memory(Arc::from([])),
             elapsed_ms: 0,
             size_bytes: 0,
+            redirects: vec![],
+            timing: Timings::default(),
+            cookies: vec![],
+            encoding: None,
         }
     }
 
+    /// First value of the header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&SharedString> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
     pub fn content_type(&self) -> &'static str {
         match &self.content {
             ResponseContent::Text(_) => "Text",
-            ResponseContent::Json(_) => "JSON",
+            ResponseContent::Json(..) => "JSON",
+            ResponseContent::Decoded(format, ..) => format.label(),
+            ResponseContent::Xml(_) => "XML",
+            ResponseContent::Html(..) => "HTML",
             ResponseContent::Image(_, _) => "Image",
-            ResponseContent::Binary(_) => "Binary",
+            ResponseContent::Pdf(_) => "PDF",
+            ResponseContent::Media(mime_type, _) if mime_type.starts_with("video/") => "Video",
+            ResponseContent::Media(..) => "Audio",
+            ResponseContent::Binary => "Binary",
+            ResponseContent::EventStream(_) => "Event Stream",
+            ResponseContent::NdJson(_) => "NDJSON",
             ResponseContent::Error(_) => "Error",
         }
     }
+}
 
-    pub fn is_json(&self) -> bool {
-        matches!(self.content, ResponseContent::Json(_))
-    }
-
-    pub fn is_image(&self) -> bool {
-        matches!(self.content, ResponseContent::Image(_, _))
-    }
+pub fn collect_headers(headers: &reqwest::header::HeaderMap) -> Vec<(SharedString, SharedString)> {
+    headers
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.as_str().to_string().into(),
+                headers::value_text(v.as_bytes()).into(),
+            )
+        })
+        .collect()
+}
 
-    pub fn is_text(&self) -> bool {
-        matches!(self.content, ResponseContent::Text(_))
-    }
+pub fn collect_set_cookies(
+    headers: &reqwest::header::HeaderMap,
+    url: &reqwest::Url,
+) -> Vec<StoredCookie> {
+    headers
+        .get_all(reqwest::header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(|v| StoredCookie::from_set_cookie(v, url))
+        .collect()
 }
 
-pub fn parse_response_content(content_type: Option<&str>, body_bytes: Vec<u8>) -> ResponseContent {
+/// Classifies the body by its content type and decodes text in its detected
+/// charset, or in `manual` when set. `truncated` marks a preview of a larger
+/// spooled body, which is never shown as an image and may end in the middle
+/// of a multi-byte sequence.
+pub fn parse_response_content(
+    content_type: Option<&str>,
+    body_bytes: Arc<[u8]>,
+    truncated: bool,
+    manual: Option<&'static Encoding>,
+) -> (ResponseContent, Option<TextEncoding>) {
     let content_type_str = content_type.unwrap_or("text/plain");
 
     // Check for image types
     if content_type_str.starts_with("image/") {
-        return ResponseContent::Image(body_bytes, content_type_str.to_string().into());
+        if truncated {
+            return (ResponseContent::Binary, None);
+        }
+        let content = ResponseContent::Image(body_bytes, content_type_str.to_string().into());
+        return (content, None);
+    }
+
+    // Documents and media are described rather than shown; a truncated body
+    // still has its headers
+    let essence = content_type_str.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    if essence == "application/pdf" {
+        let mut info = pdf::info(&body_bytes);
+        if truncated {
+            info.pages = None;
+        }
+        return (ResponseContent::Pdf(Arc::new(info)), None);
+    }
+    if essence.starts_with("audio/") || essence.starts_with("video/") {
+        let info = media::info(&body_bytes, !truncated);
+        return (ResponseContent::Media(essence.into(), Arc::new(info)), None);
     }
 
-    // Try to parse as text-based content
-    match String::from_utf8(body_bytes.clone()) {
-        Ok(text) => {
-            // Check for JSON
-            if content_type_str.contains("json") || content_type_str.contains("application/json") {
-                // Try to pretty-print JSON
-                match serde_json::from_str::<Value>(&text) {
-                    Ok(json) => match serde_json::to_string_pretty(&json) {
-                        Ok(formatted) => ResponseContent::Json(formatted.into()),
-                        Err(_) => ResponseContent::Json(text.into()),
-                    },
-                    Err(_) => ResponseContent::Text(text.into()),
-                }
-            } else if content_type_str.contains("text/")
-                || content_type_str.contains("xml")
-                || content_type_str.contains("html")
-            {
-                ResponseContent::Text(text.into())
-            } else {
-                // Try to detect JSON by parsing
-                match serde_json::from_str::<Value>(&text) {
-                    Ok(json) => match serde_json::to_string_pretty(&json) {
-                        Ok(formatted) => ResponseContent::Json(formatted.into()),
-                        Err(_) => ResponseContent::Text(text.into()),
-                    },
-                    Err(_) => ResponseContent::Text(text.into()),
-                }
+    // Decode known binary encodings; bodies that fail to decode stay binary
+    if let Some(format) = BinaryFormat::from_content_type(content_type_str) {
+        if truncated {
+            return (ResponseContent::Binary, None);
+        }
+        let content = match format.decode(&body_bytes) {
+            Ok(value) => decoded_content(format, value),
+            Err(_) => ResponseContent::Binary,
+        };
+        return (content, None);
+    }
+
+    let encoding = charset::detect(content_type, &body_bytes, manual);
+    let text = if encoding.encoding == encoding_rs::UTF_8 {
+        let text_bytes = if truncated {
+            trim_partial_utf8(&body_bytes)
+        } else {
+            &body_bytes[..]
+        };
+        match std::str::from_utf8(text_bytes) {
+            Ok(text) => Cow::Borrowed(text.strip_prefix('\u{feff}').unwrap_or(text)),
+            // Undeclared bytes that are not UTF-8 are treated as binary
+            Err(_) if encoding.source == EncodingSource::Default => {
+                return (ResponseContent::Binary, None);
             }
+            Err(_) => String::from_utf8_lossy(text_bytes),
+        }
+    } else {
+        charset::decode(&body_bytes, encoding.encoding)
+    };
+
+    (classify_text(content_type_str, &text), Some(encoding))
+}
+
+fn classify_text(content_type_str: &str, text: &str) -> ResponseContent {
+    // Check for JSON
+    if content_type_str.contains("json") || content_type_str.contains("application/json") {
+        // Try to pretty-print JSON
+        match serde_json::from_str::<Value>(text) {
+            Ok(json) => match serde_json::to_string_pretty(&json) {
+                Ok(formatted) => ResponseContent::Json(formatted.into(), Arc::new(json)),
+                Err(_) => ResponseContent::Json(text.to_string().into(), Arc::new(json)),
+            },
+            Err(_) => ResponseContent::Text(text.to_string().into()),
         }
-        Err(_) => ResponseContent::Binary(body_bytes),
+    } else if content_type_str.contains("html") {
+        ResponseContent::Html(
+            markup::pretty_print(text, true).into(),
+            markup::html_preview(text).into(),
+        )
+    } else if content_type_str.contains("xml") {
+        ResponseContent::Xml(markup::pretty_print(text, false).into())
+    } else if content_type_str.contains("text/") {
+        ResponseContent::Text(text.to_string().into())
+    } else {
+        // Try to detect JSON by parsing
+        match serde_json::from_str::<Value>(text) {
+            Ok(json) => match serde_json::to_string_pretty(&json) {
+                Ok(formatted) => ResponseContent::Json(formatted.into(), Arc::new(json)),
+                Err(_) => ResponseContent::Text(text.to_string().into()),
+            },
+            Err(_) => ResponseContent::Text(text.to_string().into()),
+        }
+    }
+}
+
+pub fn decoded_content(format: BinaryFormat, value: Value) -> ResponseContent {
+    let pretty = serde_json::to_string_pretty(&value).unwrap_or_default();
+    ResponseContent::Decoded(format, pretty.into(), Arc::new(value))
+}
+
+/// Drops an incomplete UTF-8 sequence at the end of a truncated body.
+fn trim_partial_utf8(bytes: &[u8]) -> &[u8] {
+    match std::str::from_utf8(bytes) {
+        Err(e) if e.error_len().is_none() => &bytes[..e.valid_up_to()],
+        _ => bytes,
     }
 }
@@ -0,0 +1,80 @@
+use crate::text_lines::TextLines;
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+/// Upper bound on collected matches, so a one-letter query over a huge body
+/// stays responsive.
+pub const MAX_MATCHES: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub case_sensitive: bool,
+    pub regex: bool,
+}
+
+/// A match on one line of the searched view; `range` is relative to the line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchHit {
+    pub line: usize,
+    pub range: Range<usize>,
+}
+
+pub struct Matcher(Regex);
+
+impl Matcher {
+    pub fn new(query: &str, options: SearchOptions) -> Result<Self, String> {
+        let pattern = if options.regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!options.case_sensitive)
+            .build()
+            .map(Matcher)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Non-empty matches in `text`, in order.
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        self.0
+            .find_iter(text)
+            .map(|m| m.range())
+            .filter(|range| !range.is_empty())
+            .take(MAX_MATCHES)
+            .collect()
+    }
+}
+
+/// Matches in a multi-line text, split into per-line hits. A match that spans
+/// lines is cut at the end of its first line.
+pub fn text_hits(matcher: &Matcher, lines: &TextLines) -> Vec<SearchHit> {
+    matcher
+        .find_all(&lines.text)
+        .into_iter()
+        .map(|range| {
+            let line = lines.line_at(range.start);
+            let line_range = lines.range(line);
+            SearchHit {
+                line,
+                range: range.start - line_range.start
+                    ..range.end.min(line_range.end) - line_range.start,
+            }
+        })
+        .filter(|hit| !hit.range.is_empty())
+        .collect()
+}
+
+/// Matches in a list of single-line rows.
+pub fn row_hits(matcher: &Matcher, rows: &[impl AsRef<str>]) -> Vec<SearchHit> {
+    rows.iter()
+        .enumerate()
+        .flat_map(|(line, row)| {
+            matcher
+                .find_all(row.as_ref())
+                .into_iter()
+                .map(move |range| SearchHit { line, range })
+        })
+        .take(MAX_MATCHES)
+        .collect()
+}
@@ -0,0 +1,142 @@
+use crate::response::{Response, ResponseBody, parse_response_content};
+use crate::timing::Timings;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A response pinned to a request under a name. The body is kept in its own
+/// file next to the index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub name: String,
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+    pub elapsed_ms: u64,
+}
+
+/// Snapshots of every request, keyed by `METHOD url`.
+#[derive(Default, Serialize, Deserialize)]
+struct SnapshotIndex {
+    requests: BTreeMap<String, Vec<Snapshot>>,
+}
+
+/// Pinned responses, persisted in the user's data directory.
+pub struct SnapshotStore {
+    index: SnapshotIndex,
+    dir: Option<PathBuf>,
+}
+
+impl SnapshotStore {
+    pub fn load() -> Self {
+        let dir = dirs::data_dir().map(|dir| dir.join("resty").join("snapshots"));
+        let index = dir
+            .as_ref()
+            .and_then(|dir| std::fs::read(dir.join("index.json")).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self { index, dir }
+    }
+
+    /// Snapshots of `request`, oldest first.
+    pub fn for_request(&self, request: &str) -> &[Snapshot] {
+        self.index.requests.get(request).map_or(&[], Vec::as_slice)
+    }
+
+    /// Pins `response` to `request`, writing its full body to disk.
+    pub fn pin(&mut self, request: &str, name: String, response: &Response) -> Result<(), String> {
+        let dir = self.dir.as_ref().ok_or("No data directory to store snapshots in")?;
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        let id = format!(
+            "{:x}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        );
+        let body_path = dir.join(format!("{}.body", id));
+        match &response.body {
+            ResponseBody::Memory(bytes) => std::fs::write(&body_path, bytes).map(|_| ()),
+            ResponseBody::Spooled { file, .. } => std::fs::copy(&**file, &body_path).map(|_| ()),
+        }
+        .map_err(|e| format!("Failed to save the snapshot body: {}", e))?;
+
+        self.index
+            .requests
+            .entry(request.to_string())
+            .or_default()
+            .push(Snapshot {
+                id,
+                name,
+                status: response.status,
+                status_text: response.status_text.to_string(),
+                headers: response
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                elapsed_ms: response.elapsed_ms,
+            });
+        self.save();
+        Ok(())
+    }
+
+    pub fn remove(&mut self, request: &str, id: &str) {
+        let Some(snapshots) = self.index.requests.get_mut(request) else {
+            return;
+        };
+        snapshots.retain(|snapshot| snapshot.id != id);
+        if snapshots.is_empty() {
+            self.index.requests.remove(request);
+        }
+        if let Some(dir) = &self.dir {
+            let _ = std::fs::remove_file(dir.join(format!("{}.body", id)));
+        }
+        self.save();
+    }
+
+    /// Rebuilds the pinned response from its stored body.
+    pub fn response(&self, snapshot: &Snapshot) -> Result<Response, String> {
+        let dir = self.dir.as_ref().ok_or("No data directory to load snapshots from")?;
+        let bytes: Arc<[u8]> = std::fs::read(dir.join(format!("{}.body", snapshot.id)))
+            .map_err(|e| format!("Failed to read the snapshot body: {}", e))?
+            .into();
+        let content_type = snapshot
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.as_str());
+        let (content, encoding) = parse_response_content(content_type, bytes.clone(), false, None);
+        Ok(Response {
+            status: snapshot.status,
+            status_text: snapshot.status_text.clone().into(),
+            headers: snapshot
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone().into(), value.clone().into()))
+                .collect(),
+            content,
+            size_bytes: bytes.len(),
+            body: ResponseBody::Memory(bytes),
+            elapsed_ms: snapshot.elapsed_ms,
+            redirects: vec![],
+            timing: Timings::default(),
+            cookies: vec![],
+            encoding,
+        })
+    }
+
+    fn save(&self) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+        let Ok(json) = serde_json::to_vec_pretty(&self.index) else {
+            return;
+        };
+        let _ = std::fs::create_dir_all(dir);
+        let _ = std::fs::write(dir.join("index.json"), json);
+    }
+}
@@ -0,0 +1,115 @@
+use gpui::SharedString;
+
+/// A dispatched `text/event-stream` event.
+#[derive(Clone, Debug)]
+pub struct SseEvent {
+    pub id: Option<SharedString>,
+    pub event: SharedString,
+    pub data: SharedString,
+    pub retry: Option<u64>,
+    /// Wall-clock time (UTC) at which the event was dispatched.
+    pub received_at: SharedString,
+}
+
+/// Incremental parser for the event stream format described in the HTML
+/// living standard. Bytes can be fed in arbitrary chunks.
+#[derive(Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    /// Set after a `\r` so a directly following `\n` is not read as an empty line.
+    after_cr: bool,
+    data: String,
+    event: String,
+    id: Option<String>,
+    retry: Option<u64>,
+    reconnect_ms: Option<u64>,
+    last_event_id: Option<String>,
+}
+
+impl SseParser {
+    /// The id of the most recent event, sent back as `Last-Event-ID` on reconnect.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Reconnection delay most recently requested by the server via `retry:`.
+    pub fn reconnect_ms(&self) -> Option<u64> {
+        self.reconnect_ms
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            match byte {
+                b'\n' if self.after_cr => self.after_cr = false,
+                b'\r' | b'\n' => {
+                    self.after_cr = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                        events.push(event);
+                    }
+                }
+                _ => {
+                    self.after_cr = false;
+                    self.line.push(byte);
+                }
+            }
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "event" => self.event = value.to_string(),
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.retry = Some(retry);
+                    self.reconnect_ms = Some(retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        if let Some(id) = self.id.take() {
+            self.last_event_id = Some(id);
+        }
+        let event = std::mem::take(&mut self.event);
+        let retry = self.retry.take();
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        Some(SseEvent {
+            id: self.last_event_id.clone().map(Into::into),
+            event: if event.is_empty() {
+                "message".into()
+            } else {
+                event.into()
+            },
+            data: data.into(),
+            retry,
+            received_at: crate::timing::wall_clock(),
+        })
+    }
+}
@@ -1,3 +1,6 @@
+use crate::highlight::{self, Language};
+use crate::theme::Theme;
+use crate::undo::{EditState, UndoStack};
 use gpui::*;
 use smallvec::SmallVec;
 use std::ops::Range;
@@ -9,9 +12,9 @@ pub struct TextArea {
     pub(crate) placeholder: SharedString,
     pub(crate) selected_range: Range<usize>,
     pub(crate) selection_reversed: bool,
-    pub(crate) marked_range: Option<Range<usize>>,
     pub(crate) last_layout: Option<SmallVec<[WrappedLine; 1]>>,
     pub(crate) last_bounds: Option<Bounds<Pixels>>,
+    history: UndoStack,
 }
 
 pub enum TextAreaEvent {
@@ -28,23 +31,99 @@ impl TextArea {
             placeholder: placeholder.into(),
             selected_range: 0..0,
             selection_reversed: false,
-            marked_range: None,
             last_layout: None,
             last_bounds: None,
+            history: UndoStack::default(),
         }
     }
 
     pub fn set_text(&mut self, text: impl Into<String>, cx: &mut Context<Self>) {
-        let text = text.into();
-        let len = text.len();
-        self.content = text.clone();
+        self.content = text.into();
+        let len = self.content.len();
         self.selected_range = len..len;
-        cx.emit(TextAreaEvent::TextChanged(text));
+        self.history.clear();
+        cx.emit(TextAreaEvent::TextChanged(self.content.clone()));
         cx.notify();
     }
 
-    pub fn text(&self) -> String {
-        self.content.clone()
+    pub fn text(&self) -> SharedString {
+        self.content.clone().into()
+    }
+
+    fn edit_state(&self) -> EditState {
+        EditState {
+            text: self.content.clone(),
+            selection: self.selected_range.clone(),
+        }
+    }
+
+    /// Replaces `range` with `text`, recording the edit for undo.
+    fn replace_range(&mut self, range: Range<usize>, text: &str, cx: &mut Context<Self>) {
+        self.history.record(self.edit_state(), range.clone(), text);
+        self.content.replace_range(range.clone(), text);
+        let new_pos = range.start + text.len();
+        self.selected_range = new_pos..new_pos;
+        self.selection_reversed = false;
+        cx.emit(TextAreaEvent::TextChanged(self.content.clone()));
+        cx.notify();
+    }
+
+    fn undo(&mut self, cx: &mut Context<Self>) {
+        if let Some(state) = self.history.undo(self.edit_state()) {
+            self.restore(state, cx);
+        }
+    }
+
+    fn redo(&mut self, cx: &mut Context<Self>) {
+        if let Some(state) = self.history.redo(self.edit_state()) {
+            self.restore(state, cx);
+        }
+    }
+
+    fn restore(&mut self, state: EditState, cx: &mut Context<Self>) {
+        self.content = state.text;
+        self.selected_range = state.selection;
+        self.selection_reversed = false;
+        cx.emit(TextAreaEvent::TextChanged(self.content.clone()));
+        cx.notify();
+    }
+
+    fn select_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        if self.selection_reversed {
+            self.selected_range.start = offset;
+        } else {
+            self.selected_range.end = offset;
+        }
+
+        if self.selected_range.end < self.selected_range.start {
+            self.selection_reversed = !self.selection_reversed;
+            let start = self.selected_range.start;
+            let end = self.selected_range.end;
+            self.selected_range = end..start;
+        }
+        self.history.break_group();
+        cx.notify();
+    }
+
+    fn copy(&mut self, cx: &mut Context<Self>) {
+        if !self.selected_range.is_empty() {
+            let text = self.content[self.selected_range.clone()].to_string();
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    /// Pastes clipboard text over the selection, keeping its line breaks.
+    fn paste(&mut self, cx: &mut Context<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        let text = text.replace("\r\n", "\n");
+        if text.is_empty() {
+            return;
+        }
+        self.history.break_group();
+        self.replace_range(self.selected_range.clone(), &text, cx);
+        self.history.break_group();
     }
 
     fn cursor_offset(&self) -> usize {
@@ -58,24 +137,25 @@ impl TextArea {
     fn on_key_down(&mut self, event: &KeyDownEvent, _window: &mut Window, cx: &mut Context<Self>) {
         match event.keystroke.key.as_str() {
             "enter" => {
-                let cursor = self.cursor_offset();
-                self.content.insert(cursor, '\n');
-                let new_pos = cursor + 1;
-                self.selected_range = new_pos..new_pos;
-                cx.emit(TextAreaEvent::TextChanged(self.content.clone()));
-                cx.notify();
+                self.replace_range(self.selected_range.clone(), "\n", cx);
             }
             "left" => {
                 let mut cursor = self.cursor_offset();
                 if cursor > 0 {
                     cursor = self.content[..cursor]
                         .grapheme_indices(true)
-                        .last()
+                        .next_back()
                         .map(|(o, _)| o)
                         .unwrap_or(0);
                 }
-                self.selected_range = cursor..cursor;
-                cx.notify();
+                if event.keystroke.modifiers.shift {
+                    self.select_to(cursor, cx);
+                } else {
+                    self.selected_range = cursor..cursor;
+                    self.selection_reversed = false;
+                    self.history.break_group();
+                    cx.notify();
+                }
             }
             "right" => {
                 let mut cursor = self.cursor_offset();
@@ -86,40 +166,75 @@ impl TextArea {
                         .map(|(o, _)| cursor + o)
                         .unwrap_or(self.content.len());
                 }
-                self.selected_range = cursor..cursor;
-                cx.notify();
+                if event.keystroke.modifiers.shift {
+                    self.select_to(cursor, cx);
+                } else {
+                    self.selected_range = cursor..cursor;
+                    self.selection_reversed = false;
+                    self.history.break_group();
+                    cx.notify();
+                }
             }
             "backspace" => {
                 if !self.selected_range.is_empty() {
-                    let range = self.selected_range.clone();
-                    self.content.replace_range(range.clone(), "");
-                    self.selected_range = range.start..range.start;
-                    cx.emit(TextAreaEvent::TextChanged(self.content.clone()));
-                    cx.notify();
+                    self.replace_range(self.selected_range.clone(), "", cx);
                 } else {
                     let cursor = self.cursor_offset();
                     if cursor > 0 {
                         let prev = self.content[..cursor]
                             .grapheme_indices(true)
-                            .last()
+                            .next_back()
                             .map(|(o, _)| o)
                             .unwrap_or(0);
-                        self.content.replace_range(prev..cursor, "");
-                        self.selected_range = prev..prev;
-                        cx.emit(TextAreaEvent::TextChanged(self.content.clone()));
-                        cx.notify();
+                        self.replace_range(prev..cursor, "", cx);
+                    }
+                }
+            }
+            "delete" => {
+                if !self.selected_range.is_empty() {
+                    self.replace_range(self.selected_range.clone(), "", cx);
+                } else {
+                    let cursor = self.cursor_offset();
+                    if cursor < self.content.len() {
+                        let next = self.content[cursor..]
+                            .grapheme_indices(true)
+                            .nth(1)
+                            .map(|(o, _)| cursor + o)
+                            .unwrap_or(self.content.len());
+                        self.replace_range(cursor..next, "", cx);
                     }
                 }
             }
+            "a" if event.keystroke.modifiers.platform || event.keystroke.modifiers.control => {
+                self.selected_range = 0..self.content.len();
+                self.selection_reversed = false;
+                self.history.break_group();
+                cx.notify();
+            }
+            "c" if event.keystroke.modifiers.platform || event.keystroke.modifiers.control => {
+                self.copy(cx);
+            }
+            "x" if (event.keystroke.modifiers.platform || event.keystroke.modifiers.control)
+                && !self.selected_range.is_empty() =>
+            {
+                self.copy(cx);
+                self.replace_range(self.selected_range.clone(), "", cx);
+            }
+            "v" if event.keystroke.modifiers.platform || event.keystroke.modifiers.control => {
+                self.paste(cx);
+            }
+            "z" if event.keystroke.modifiers.platform || event.keystroke.modifiers.control => {
+                if event.keystroke.modifiers.shift {
+                    self.redo(cx);
+                } else {
+                    self.undo(cx);
+                }
+            }
+            "y" if event.keystroke.modifiers.control => self.redo(cx),
             _ => {
                 let key = event.keystroke.key.as_str();
                 if key.len() == 1 && !event.keystroke.modifiers.control && !event.keystroke.modifiers.platform {
-                    let cursor = self.cursor_offset();
-                    self.content.insert_str(cursor, key);
-                    let new_pos = cursor + key.len();
-                    self.selected_range = new_pos..new_pos;
-                    cx.emit(TextAreaEvent::TextChanged(self.content.clone()));
-                    cx.notify();
+                    self.replace_range(self.selected_range.clone(), key, cx);
                 }
             }
         }
@@ -190,13 +305,20 @@ impl Element for TextAreaElement {
         };
 
         let content = input.read(cx).content.clone();
-        let display_text = if content.is_empty() {
+        let display_text: SharedString = if content.is_empty() {
             input.read(cx).placeholder.clone()
         } else {
-            content.into()
+            content.clone().into()
         };
 
-        let text_runs = [text_style.to_run(display_text.len())];
+        // Highlight the body as JSON or markup once it looks like either
+        let language = if content.is_empty() {
+            None
+        } else {
+            Language::detect(&content)
+        };
+        let theme = Theme::dark();
+        let text_runs = highlight::text_runs(&display_text, language, &text_style, &theme);
         let shaped_text = window.text_system().shape_text(
             display_text,
             px(14.),
@@ -211,12 +333,26 @@ impl Element for TextAreaElement {
         });
 
         let line_height = window.line_height();
-        for (i, line) in shaped_text.iter().enumerate() {
-            let line_origin = point(bounds.left(), bounds.top() + line_height * i);
+        let is_focused = focus_handle.is_focused(window);
+        let selected_range = input.read(cx).selected_range.clone();
+        // Wrapped lines take several rows, so track the offset and top of each logical line
+        let mut line_start = 0;
+        let mut line_top = bounds.top();
+        for line in shaped_text.iter() {
+            let line_origin = point(bounds.left(), line_top);
+            if is_focused && !content.is_empty() {
+                let start = selected_range.start.max(line_start);
+                let end = selected_range.end.min(line_start + line.len());
+                if start < end {
+                    paint_selection(line, start - line_start..end - line_start, line_origin, bounds.size.width, line_height, window);
+                }
+            }
             line.paint(line_origin, line_height, TextAlign::Left, None, window, cx).unwrap();
+            line_start += line.len() + 1;
+            line_top += line.size(line_height).height;
         }
 
-        if focus_handle.is_focused(window) {
+        if is_focused {
             let cursor_bounds = Bounds {
                 origin: bounds.origin,
                 size: size(px(2.), line_height),
@@ -225,3 +361,24 @@ impl Element for TextAreaElement {
         }
     }
 }
+
+/// Paints the selection highlight for `range` within one logical line, row by row.
+fn paint_selection(line: &WrappedLine, range: Range<usize>, origin: Point<Pixels>, width: Pixels, line_height: Pixels, window: &mut Window) {
+    let (Some(start), Some(end)) = (
+        line.position_for_index(range.start, line_height),
+        line.position_for_index(range.end, line_height),
+    ) else {
+        return;
+    };
+    let first_row = (start.y / line_height).round() as usize;
+    let last_row = (end.y / line_height).round() as usize;
+    for row in first_row..=last_row {
+        let left = if row == first_row { start.x } else { px(0.) };
+        let right = if row == last_row { end.x } else { width };
+        let selection_bounds = Bounds {
+            origin: point(origin.x + left, origin.y + line_height * row),
+            size: size(right - left, line_height),
+        };
+        window.paint_quad(fill(selection_bounds, rgba(0x007acc88)));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::undo::{EditState, UndoStack};
 use gpui::*;
 use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
@@ -12,6 +13,7 @@ pub struct TextInput {
     last_layout: Option<ShapedLine>,
     last_bounds: Option<Bounds<Pixels>>,
     is_selecting: bool,
+    history: UndoStack,
 }
 
 pub enum TextInputEvent {
@@ -33,6 +35,7 @@ impl TextInput {
             last_layout: None,
             last_bounds: None,
             is_selecting: false,
+            history: UndoStack::default(),
         }
     }
 
@@ -41,6 +44,7 @@ impl TextInput {
         let len = text.len();
         self.content = text;
         self.selected_range = len..len;
+        self.history.clear();
         cx.notify();
     }
 
@@ -48,6 +52,34 @@ impl TextInput {
         self.content.clone().into()
     }
 
+    fn edit_state(&self) -> EditState {
+        EditState {
+            text: self.content.clone(),
+            selection: self.selected_range.clone(),
+        }
+    }
+
+    fn undo(&mut self, cx: &mut Context<Self>) {
+        if let Some(state) = self.history.undo(self.edit_state()) {
+            self.restore(state, cx);
+        }
+    }
+
+    fn redo(&mut self, cx: &mut Context<Self>) {
+        if let Some(state) = self.history.redo(self.edit_state()) {
+            self.restore(state, cx);
+        }
+    }
+
+    fn restore(&mut self, state: EditState, cx: &mut Context<Self>) {
+        self.content = state.text;
+        self.selected_range = state.selection;
+        self.selection_reversed = false;
+        self.marked_range = None;
+        cx.emit(TextInputEvent::TextChanged(self.content.clone()));
+        cx.notify();
+    }
+
     fn cursor_offset(&self) -> usize {
         if self.selection_reversed {
             self.selected_range.start
@@ -81,7 +113,8 @@ impl TextInput {
 
     fn move_to(&mut self, offset: usize, cx: &mut Context<Self>) {
         self.selected_range = offset..offset;
-        self.selection_reversed = false;        let content = self.content.clone();
+        self.selection_reversed = false;
+        self.history.break_group();        let content = self.content.clone();
         cx.emit(TextInputEvent::TextChanged(content));        cx.notify();
     }
 
@@ -98,6 +131,7 @@ impl TextInput {
             let end = self.selected_range.end;
             self.selected_range = end..start;
         }
+        self.history.break_group();
         cx.notify();
     }
 
@@ -111,7 +145,7 @@ impl TextInput {
                 if cursor > 0 {
                     cursor = self.content[..cursor]
                         .grapheme_indices(true)
-                        .last()
+                        .next_back()
                         .map(|(o, _)| o)
                         .unwrap_or(0);
                 }
@@ -144,7 +178,7 @@ impl TextInput {
                     if cursor > 0 {
                         let prev = self.content[..cursor]
                             .grapheme_indices(true)
-                            .last()
+                            .next_back()
                             .map(|(o, _)| o)
                             .unwrap_or(0);
                         self.replace_text_in_range(Some(prev..cursor), "", window, cx);
@@ -171,10 +205,51 @@ impl TextInput {
                 self.selection_reversed = false;
                 cx.notify();
             }
+            "c" if event.keystroke.modifiers.platform || event.keystroke.modifiers.control => {
+                self.copy(cx);
+            }
+            "x" if (event.keystroke.modifiers.platform || event.keystroke.modifiers.control)
+                && !self.selected_range.is_empty() =>
+            {
+                self.copy(cx);
+                self.replace_text_in_range(None, "", window, cx);
+            }
+            "v" if event.keystroke.modifiers.platform || event.keystroke.modifiers.control => {
+                self.paste(window, cx);
+            }
+            "z" if event.keystroke.modifiers.platform || event.keystroke.modifiers.control => {
+                if event.keystroke.modifiers.shift {
+                    self.redo(cx);
+                } else {
+                    self.undo(cx);
+                }
+            }
+            "y" if event.keystroke.modifiers.control => self.redo(cx),
             _ => {}
         }
     }
 
+    fn copy(&mut self, cx: &mut Context<Self>) {
+        if !self.selected_range.is_empty() {
+            let text = self.content[self.selected_range.clone()].to_string();
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    /// Pastes clipboard text over the selection; line breaks are dropped since the input is single-line.
+    fn paste(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        let text = text.replace(['\r', '\n'], "");
+        if text.is_empty() {
+            return;
+        }
+        self.history.break_group();
+        self.replace_text_in_range(None, &text, window, cx);
+        self.history.break_group();
+    }
+
     fn on_mouse_down(
         &mut self,
         event: &MouseDownEvent,
@@ -279,11 +354,16 @@ impl EntityInputHandler for TextInput {
         let end = range.end.min(self.content.len());
         let range = start..end;
 
+        // Committing IME text belongs to the step recorded when composition started
+        if self.marked_range.is_none() {
+            self.history.record(self.edit_state(), range.clone(), text);
+        }
         self.content.replace_range(range.clone(), text);
         let new_offset = range.start + text.len();
         self.selected_range = new_offset..new_offset;
         self.selection_reversed = false;
         self.marked_range = None;
+        cx.emit(TextInputEvent::TextChanged(self.content.clone()));
         cx.notify();
     }
 
@@ -307,6 +387,10 @@ impl EntityInputHandler for TextInput {
         let end = range.end.min(self.content.len());
         let range = start..end;
 
+        if self.marked_range.is_none() {
+            self.history.record(self.edit_state(), range.clone(), new_text);
+            self.history.break_group();
+        }
         self.content.replace_range(range.clone(), new_text);
         let mark_start = range.start;
         let mark_end = range.start + new_text.len();
@@ -468,7 +552,7 @@ impl Element for TextInputElement {
         let run = text_style.to_run(display_text.len());
         let layout = window
             .text_system()
-            .shape_line(display_text.into(), px(14.), &[run], None);
+            .shape_line(display_text, px(14.), &[run], None);
 
         // Save layout for interaction
         input.update(cx, |this, _| {
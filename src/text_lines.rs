@@ -0,0 +1,87 @@
+use crate::highlight::{self, Language, TokenKind};
+use gpui::SharedString;
+use std::ops::Range;
+
+/// Bodies larger than this are shown without syntax highlighting.
+pub const MAX_HIGHLIGHT_BYTES: usize = 8 * 1024 * 1024;
+
+/// A response text split into lines once, so views only lay out the lines
+/// that are on screen.
+pub struct TextLines {
+    pub text: SharedString,
+    /// Byte offset where each line starts.
+    starts: Vec<usize>,
+    /// Index of the longest line, which sizes the view when lines don't wrap.
+    pub longest: usize,
+    tokens: Vec<(Range<usize>, TokenKind)>,
+}
+
+impl TextLines {
+    pub fn new(text: SharedString, language: Option<Language>) -> Self {
+        let starts: Vec<usize> = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let tokens = match language {
+            Some(language) if text.len() <= MAX_HIGHLIGHT_BYTES => {
+                highlight::tokenize(&text, language)
+            }
+            _ => Vec::new(),
+        };
+        let mut lines = Self {
+            text,
+            starts,
+            longest: 0,
+            tokens,
+        };
+        lines.longest = (0..lines.len())
+            .max_by_key(|&ix| lines.range(ix).len())
+            .unwrap_or(0);
+        lines
+    }
+
+    /// Whether these lines were built from `text` itself.
+    pub fn is_for(&self, text: &SharedString) -> bool {
+        self.text.as_ptr() == text.as_ptr() && self.text.len() == text.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Byte range of line `ix` in `text`, without its line break.
+    pub fn range(&self, ix: usize) -> Range<usize> {
+        let start = self.starts[ix];
+        let end = self
+            .starts
+            .get(ix + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        start..end
+    }
+
+    /// Index of the line that contains byte `offset`.
+    pub fn line_at(&self, offset: usize) -> usize {
+        self.starts.partition_point(|&start| start <= offset) - 1
+    }
+
+    pub fn line(&self, ix: usize) -> &str {
+        let line = &self.text[self.range(ix)];
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    /// Syntax tokens on line `ix`, clipped to it and relative to its start.
+    pub fn tokens(&self, ix: usize) -> Vec<(Range<usize>, TokenKind)> {
+        let range = self.range(ix);
+        let len = self.line(ix).len();
+        let first = self.tokens.partition_point(|(token, _)| token.end <= range.start);
+        self.tokens[first..]
+            .iter()
+            .take_while(|(token, _)| token.start < range.end)
+            .map(|(token, kind)| {
+                let start = token.start.max(range.start) - range.start;
+                let end = (token.end.min(range.end) - range.start).min(len);
+                (start..end, *kind)
+            })
+            .filter(|(token, _)| !token.is_empty())
+            .collect()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::highlight::TokenKind;
 use gpui::*;
 
 pub struct Theme {
@@ -10,6 +11,20 @@ pub struct Theme {
     pub text_dim: Hsla,
     pub accent: Hsla,
     pub accent_text: Hsla,
+    // Syntax colors for highlighted response views
+    pub syntax_key: Hsla,
+    pub syntax_string: Hsla,
+    pub syntax_number: Hsla,
+    pub syntax_keyword: Hsla,
+    pub syntax_tag: Hsla,
+    pub syntax_attribute: Hsla,
+    pub syntax_comment: Hsla,
+    // Find-in-response hits
+    pub search_match: Hsla,
+    pub search_match_current: Hsla,
+    // Response comparison
+    pub diff_added: Hsla,
+    pub diff_removed: Hsla,
 }
 
 impl Theme {
@@ -24,6 +39,29 @@ impl Theme {
             text_dim: rgb(0x999999).into(),
             accent: rgb(0x007acc).into(),
             accent_text: rgb(0xffffff).into(),
+            syntax_key: rgb(0x9cdcfe).into(),
+            syntax_string: rgb(0xce9178).into(),
+            syntax_number: rgb(0xb5cea8).into(),
+            syntax_keyword: rgb(0x569cd6).into(),
+            syntax_tag: rgb(0x4ec9b0).into(),
+            syntax_attribute: rgb(0x9cdcfe).into(),
+            syntax_comment: rgb(0x6a9955).into(),
+            search_match: rgba(0xea5c0055).into(),
+            search_match_current: rgb(0x9e6a03).into(),
+            diff_added: rgba(0x2ea04340).into(),
+            diff_removed: rgba(0xf8514940).into(),
+        }
+    }
+
+    pub fn syntax_color(&self, kind: TokenKind) -> Hsla {
+        match kind {
+            TokenKind::Key => self.syntax_key,
+            TokenKind::String => self.syntax_string,
+            TokenKind::Number => self.syntax_number,
+            TokenKind::Keyword => self.syntax_keyword,
+            TokenKind::Tag => self.syntax_tag,
+            TokenKind::Attribute => self.syntax_attribute,
+            TokenKind::Comment => self.syntax_comment,
         }
     }
 }
@@ -0,0 +1,190 @@
+use gpui::SharedString;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_layer::Layer;
+use tower_service::Service;
+
+tokio::task_local! {
+    /// Connection phases of the request currently being sent on this task.
+    static CONNECT_PHASES: Arc<Mutex<ConnectPhases>>;
+}
+
+/// Durations observed while a new connection was established. Both stay
+/// `None` when the request reused a pooled connection.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConnectPhases {
+    pub dns: Option<Duration>,
+    /// Total time spent in the connector, which includes DNS, TCP and TLS.
+    pub connect: Option<Duration>,
+}
+
+/// Runs `fut` with a fresh phase recorder and returns what the resolver and
+/// connector layer observed alongside its output.
+pub async fn record_phases<F: Future>(fut: F) -> (F::Output, ConnectPhases) {
+    let phases = Arc::new(Mutex::new(ConnectPhases::default()));
+    let output = CONNECT_PHASES.scope(phases.clone(), fut).await;
+    let phases = *phases.lock().unwrap();
+    (output, phases)
+}
+
+fn current_phases() -> Option<Arc<Mutex<ConnectPhases>>> {
+    CONNECT_PHASES.try_with(Arc::clone).ok()
+}
+
+/// DNS resolver that records how long the lookup took.
+pub struct TimedResolver;
+
+impl Resolve for TimedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let phases = current_phases();
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let start = Instant::now();
+            let addrs: Vec<_> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Some(phases) = phases {
+                phases.lock().unwrap().dns = Some(start.elapsed());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Connector layer that records how long establishing a connection took.
+#[derive(Clone)]
+pub struct ConnectTimingLayer;
+
+impl<S> Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct ConnectTiming<S> {
+    inner: S,
+}
+
+impl<S, R> Service<R> for ConnectTiming<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let phases = current_phases();
+        let start = Instant::now();
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let result = fut.await;
+            if let (Some(phases), Ok(_)) = (phases, &result) {
+                phases.lock().unwrap().connect = Some(start.elapsed());
+            }
+            result
+        })
+    }
+}
+
+/// Peer certificate summary of the TLS connection.
+#[derive(Clone, Debug)]
+pub struct TlsDetails {
+    pub subject: SharedString,
+    pub issuer: SharedString,
+    pub not_after: SharedString,
+}
+
+impl TlsDetails {
+    pub fn from_der(der: &[u8]) -> Option<Self> {
+        let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+        Some(Self {
+            subject: cert.subject().to_string().into(),
+            issuer: cert.issuer().to_string().into(),
+            not_after: cert.validity().not_after.to_string().into(),
+        })
+    }
+}
+
+/// Phase breakdown of the final request in milliseconds.
+#[derive(Clone, Debug, Default)]
+pub struct Timings {
+    /// Time spent on earlier hops of a redirect chain.
+    pub redirect_ms: f64,
+    pub dns_ms: Option<f64>,
+    /// TCP connect plus TLS handshake, excluding DNS.
+    pub connect_ms: Option<f64>,
+    /// Time from sending the request to receiving the response headers.
+    pub ttfb_ms: f64,
+    pub download_ms: f64,
+    pub remote_addr: Option<SharedString>,
+    pub http_version: SharedString,
+    pub tls: Option<TlsDetails>,
+}
+
+impl Timings {
+    pub fn new(phases: ConnectPhases, headers_received: Duration) -> Self {
+        let dns = phases.dns.unwrap_or_default();
+        let connect = phases.connect.map(|connect| connect.saturating_sub(dns));
+        let established = phases.connect.unwrap_or_default();
+        Self {
+            dns_ms: phases.dns.map(as_ms),
+            connect_ms: connect.map(as_ms),
+            ttfb_ms: as_ms(headers_received.saturating_sub(established)),
+            ..Default::default()
+        }
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.redirect_ms
+            + self.dns_ms.unwrap_or(0.0)
+            + self.connect_ms.unwrap_or(0.0)
+            + self.ttfb_ms
+            + self.download_ms
+    }
+
+    /// Waterfall rows as `(label, offset_ms, duration_ms)`.
+    pub fn phases(&self) -> Vec<(&'static str, f64, f64)> {
+        let mut rows = Vec::new();
+        let mut offset = 0.0;
+        let mut push = |label, duration: Option<f64>| {
+            if let Some(duration) = duration {
+                rows.push((label, offset, duration));
+                offset += duration;
+            }
+        };
+        push("Redirects", (self.redirect_ms > 0.0).then_some(self.redirect_ms));
+        push("DNS lookup", self.dns_ms);
+        push("Connect (TCP + TLS)", self.connect_ms);
+        push("Waiting (TTFB)", Some(self.ttfb_ms));
+        push("Content download", Some(self.download_ms));
+        rows
+    }
+}
+
+pub fn as_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Current UTC time of day as `HH:MM:SS.mmm`, used to timestamp log entries.
+pub fn wall_clock() -> SharedString {
+    let now = time::OffsetDateTime::now_utc();
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        now.hour(),
+        now.minute(),
+        now.second(),
+        now.millisecond()
+    )
+    .into()
+}
@@ -0,0 +1,233 @@
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Number of undo steps kept per text widget.
+const UNDO_LIMIT: usize = 100;
+
+/// Text and selection of a widget at one point in its edit history.
+#[derive(Clone)]
+pub struct EditState {
+    pub text: String,
+    pub selection: Range<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum EditKind {
+    Typing,
+    Deleting,
+}
+
+/// Undo/redo stacks for a single text widget.
+///
+/// Consecutive single-grapheme insertions at the cursor are grouped into one
+/// step, as are consecutive backspace/delete presses; whitespace starts a new
+/// typing group so undo walks back roughly word by word.
+#[derive(Default)]
+pub struct UndoStack {
+    undo: Vec<EditState>,
+    redo: Vec<EditState>,
+    // Kind of the open group and the offset where the next edit must land to join it
+    group: Option<(EditKind, usize)>,
+}
+
+impl UndoStack {
+    /// Records the state before replacing `range` with `inserted`.
+    pub fn record(&mut self, before: EditState, range: Range<usize>, inserted: &str) {
+        let collapsed = before.selection.is_empty();
+        let kind = if collapsed && range.is_empty() && is_single_grapheme(inserted) {
+            Some(EditKind::Typing)
+        } else if collapsed && inserted.is_empty() && !range.is_empty() {
+            Some(EditKind::Deleting)
+        } else {
+            None
+        };
+
+        let joins = match (kind, self.group) {
+            (Some(EditKind::Typing), Some((EditKind::Typing, at))) => {
+                at == range.start && !inserted.chars().all(char::is_whitespace)
+            }
+            // Backspace ends where the previous deletion started, delete starts there
+            (Some(EditKind::Deleting), Some((EditKind::Deleting, at))) => {
+                at == range.end || at == range.start
+            }
+            _ => false,
+        };
+
+        if !joins {
+            self.undo.push(before);
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.group = kind.map(|kind| (kind, range.start + inserted.len()));
+    }
+
+    /// Returns the state to restore, saving `current` for redo.
+    pub fn undo(&mut self, current: EditState) -> Option<EditState> {
+        let state = self.undo.pop()?;
+        self.redo.push(current);
+        self.group = None;
+        Some(state)
+    }
+
+    /// Returns the state to restore, saving `current` for undo.
+    pub fn redo(&mut self, current: EditState) -> Option<EditState> {
+        let state = self.redo.pop()?;
+        self.undo.push(current);
+        self.group = None;
+        Some(state)
+    }
+
+    /// Closes the open typing group, e.g. after the cursor was moved.
+    pub fn break_group(&mut self) {
+        self.group = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group = None;
+    }
+}
+
+fn is_single_grapheme(text: &str) -> bool {
+    text.graphemes(true).count() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A single-line editor with a collapsed cursor, enough to drive the stack.
+    struct Editor {
+        text: String,
+        cursor: usize,
+        stack: UndoStack,
+    }
+
+    impl Editor {
+        fn new(text: &str) -> Self {
+            Self {
+                text: text.to_string(),
+                cursor: text.len(),
+                stack: UndoStack::default(),
+            }
+        }
+
+        fn state(&self) -> EditState {
+            EditState {
+                text: self.text.clone(),
+                selection: self.cursor..self.cursor,
+            }
+        }
+
+        fn replace(&mut self, range: Range<usize>, inserted: &str) {
+            self.stack.record(self.state(), range.clone(), inserted);
+            self.text.replace_range(range.clone(), inserted);
+            self.cursor = range.start + inserted.len();
+        }
+
+        fn type_text(&mut self, text: &str) {
+            for grapheme in text.graphemes(true) {
+                self.replace(self.cursor..self.cursor, grapheme);
+            }
+        }
+
+        fn backspace(&mut self) {
+            self.replace(self.cursor - 1..self.cursor, "");
+        }
+
+        fn delete(&mut self) {
+            self.replace(self.cursor..self.cursor + 1, "");
+        }
+
+        fn undo(&mut self) {
+            let state = self.stack.undo(self.state()).expect("nothing to undo");
+            self.text = state.text;
+            self.cursor = state.selection.end;
+        }
+
+        fn redo(&mut self) {
+            let state = self.stack.redo(self.state()).expect("nothing to redo");
+            self.text = state.text;
+            self.cursor = state.selection.end;
+        }
+    }
+
+    #[test]
+    fn typing_is_undone_word_by_word() {
+        let mut editor = Editor::new("");
+        editor.type_text("hello world");
+        editor.undo();
+        assert_eq!(editor.text, "hello");
+        editor.undo();
+        assert_eq!(editor.text, "");
+    }
+
+    #[test]
+    fn backspaces_join_into_one_step() {
+        let mut editor = Editor::new("abcdef");
+        editor.backspace();
+        editor.backspace();
+        editor.backspace();
+        assert_eq!(editor.text, "abc");
+        editor.undo();
+        assert_eq!(editor.text, "abcdef");
+    }
+
+    #[test]
+    fn deletes_join_into_one_step() {
+        let mut editor = Editor::new("abcdef");
+        editor.cursor = 0;
+        editor.delete();
+        editor.delete();
+        editor.delete();
+        assert_eq!(editor.text, "def");
+        editor.undo();
+        assert_eq!(editor.text, "abcdef");
+    }
+
+    #[test]
+    fn typing_and_deleting_are_separate_steps() {
+        let mut editor = Editor::new("");
+        editor.type_text("abc");
+        editor.backspace();
+        editor.undo();
+        assert_eq!(editor.text, "abc");
+        editor.undo();
+        assert_eq!(editor.text, "");
+    }
+
+    #[test]
+    fn moving_the_cursor_starts_a_new_step() {
+        let mut editor = Editor::new("");
+        editor.type_text("ab");
+        editor.cursor = 0;
+        editor.stack.break_group();
+        editor.type_text("x");
+        editor.undo();
+        assert_eq!(editor.text, "ab");
+    }
+
+    #[test]
+    fn typing_elsewhere_starts_a_new_step() {
+        let mut editor = Editor::new("");
+        editor.type_text("ab");
+        editor.cursor = 0;
+        editor.type_text("x");
+        editor.undo();
+        assert_eq!(editor.text, "ab");
+    }
+
+    #[test]
+    fn redo_restores_the_undone_step() {
+        let mut editor = Editor::new("");
+        editor.type_text("hello world");
+        editor.undo();
+        editor.redo();
+        assert_eq!(editor.text, "hello world");
+        editor.type_text("!");
+        assert!(editor.stack.redo(editor.state()).is_none());
+    }
+}
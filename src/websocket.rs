@@ -0,0 +1,360 @@
+use crate::timing::wall_clock;
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures::{SinkExt, StreamExt};
+use gpui::SharedString;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+/// How long to wait for the server to answer our close frame.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Binary payloads are logged as hex up to this many bytes.
+const HEX_PREVIEW_BYTES: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Sent,
+    Received,
+    /// Connection state changes and errors, not frames.
+    Info,
+}
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub at: SharedString,
+    pub direction: Direction,
+    pub kind: SharedString,
+    pub payload: SharedString,
+}
+
+impl LogEntry {
+    fn new(
+        direction: Direction,
+        kind: impl Into<SharedString>,
+        payload: impl Into<SharedString>,
+    ) -> Self {
+        Self {
+            at: wall_clock(),
+            direction,
+            kind: kind.into(),
+            payload: payload.into(),
+        }
+    }
+
+    pub fn info(payload: impl Into<SharedString>) -> Self {
+        Self::new(Direction::Info, "Info", payload)
+    }
+
+    fn frame(direction: Direction, message: &Message) -> Self {
+        let (kind, payload) = match message {
+            Message::Text(text) => ("Text", pretty_json(text).unwrap_or_else(|| text.to_string())),
+            Message::Binary(bytes) => ("Binary", hex_preview(bytes)),
+            Message::Ping(bytes) => ("Ping", hex_preview(bytes)),
+            Message::Pong(bytes) => ("Pong", hex_preview(bytes)),
+            Message::Close(Some(frame)) => (
+                "Close",
+                format!("{} {}", u16::from(frame.code), frame.reason)
+                    .trim_end()
+                    .to_string(),
+            ),
+            Message::Close(None) => ("Close", "No status code".to_string()),
+            Message::Frame(_) => ("Frame", String::new()),
+        };
+        Self::new(direction, kind, payload)
+    }
+}
+
+/// How the composer contents are turned into a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageFormat {
+    Text,
+    /// Hex bytes, whitespace ignored, e.g. `de ad be ef`.
+    Binary,
+    /// Validated and sent compact as a text frame.
+    Json,
+}
+
+impl MessageFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageFormat::Text => "Text",
+            MessageFormat::Binary => "Binary (hex)",
+            MessageFormat::Json => "JSON",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            MessageFormat::Text => MessageFormat::Binary,
+            MessageFormat::Binary => MessageFormat::Json,
+            MessageFormat::Json => MessageFormat::Text,
+        }
+    }
+
+    pub fn encode(self, input: &str) -> Result<Message, String> {
+        match self {
+            MessageFormat::Text => Ok(Message::text(input)),
+            MessageFormat::Binary => parse_hex(input).map(Message::binary),
+            MessageFormat::Json => serde_json::from_str::<serde_json::Value>(input)
+                .map(|value| Message::text(value.to_string()))
+                .map_err(|e| format!("Invalid JSON: {}", e)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionStatus {
+    Connecting,
+    Open,
+    Closing,
+    Closed,
+}
+
+impl SessionStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionStatus::Connecting => "Connecting",
+            SessionStatus::Open => "Open",
+            SessionStatus::Closing => "Closing",
+            SessionStatus::Closed => "Closed",
+        }
+    }
+}
+
+/// Instructions from the UI to the session task.
+pub enum Command {
+    Send(Message),
+    Close,
+}
+
+/// Updates sent from the session task back to the UI.
+pub enum SessionEvent {
+    Open { protocol: Option<SharedString> },
+    Log(LogEntry),
+    Closed,
+}
+
+/// Everything needed to open a connection, detached from the UI state.
+pub struct Handshake {
+    pub url: String,
+    pub headers: Vec<(SharedString, SharedString)>,
+    pub protocols: Vec<String>,
+}
+
+/// A WebSocket connection owned by `AppState`, with its message log.
+pub struct WebSocketSession {
+    pub id: u64,
+    pub url: SharedString,
+    pub status: SessionStatus,
+    /// Subprotocol selected by the server during the handshake.
+    pub protocol: Option<SharedString>,
+    pub log: Vec<LogEntry>,
+    commands: UnboundedSender<Command>,
+    task: tokio::task::AbortHandle,
+}
+
+impl WebSocketSession {
+    pub fn new(
+        id: u64,
+        url: SharedString,
+        commands: UnboundedSender<Command>,
+        task: tokio::task::AbortHandle,
+    ) -> Self {
+        Self {
+            id,
+            url,
+            status: SessionStatus::Connecting,
+            protocol: None,
+            log: Vec::new(),
+            commands,
+            task,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status != SessionStatus::Closed
+    }
+
+    pub fn send(&self, message: Message) {
+        let _ = self.commands.unbounded_send(Command::Send(message));
+    }
+
+    /// Starts the closing handshake; a second call drops the connection.
+    pub fn close(&mut self) {
+        match self.status {
+            SessionStatus::Open => {
+                self.status = SessionStatus::Closing;
+                let _ = self.commands.unbounded_send(Command::Close);
+            }
+            SessionStatus::Connecting | SessionStatus::Closing => {
+                self.task.abort();
+                self.status = SessionStatus::Closed;
+                self.log.push(LogEntry::info("Connection dropped"));
+            }
+            SessionStatus::Closed => {}
+        }
+    }
+}
+
+impl Drop for WebSocketSession {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Connects, then forwards commands to the socket and received frames to the
+/// UI until either side closes the connection.
+pub async fn run_session(
+    handshake: Handshake,
+    commands: UnboundedReceiver<Command>,
+    events: &UnboundedSender<SessionEvent>,
+) {
+    connect_and_relay(handshake, commands, events).await;
+    let _ = events.unbounded_send(SessionEvent::Closed);
+}
+
+async fn connect_and_relay(
+    handshake: Handshake,
+    mut commands: UnboundedReceiver<Command>,
+    events: &UnboundedSender<SessionEvent>,
+) {
+    let log = |entry: LogEntry| {
+        let _ = events.unbounded_send(SessionEvent::Log(entry));
+    };
+
+    let mut request = match handshake.url.as_str().into_client_request() {
+        Ok(request) => request,
+        Err(e) => {
+            log(LogEntry::info(format!("Invalid URL: {}", e)));
+            return;
+        }
+    };
+    for (name, value) in &handshake.headers {
+        match (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                request.headers_mut().append(name, value);
+            }
+            _ => log(LogEntry::info(format!("Skipping invalid header {}", name))),
+        }
+    }
+    if !handshake.protocols.is_empty() {
+        match HeaderValue::from_str(&handshake.protocols.join(", ")) {
+            Ok(value) => {
+                request.headers_mut().insert("Sec-WebSocket-Protocol", value);
+            }
+            Err(_) => log(LogEntry::info("Skipping invalid subprotocols")),
+        }
+    }
+
+    log(LogEntry::info(format!("Connecting to {}", handshake.url)));
+    let (socket, response) = match tokio_tungstenite::connect_async(request).await {
+        Ok(connected) => connected,
+        Err(e) => {
+            log(LogEntry::info(format!("Connection failed: {}", e)));
+            return;
+        }
+    };
+    let protocol = response
+        .headers()
+        .get("sec-websocket-protocol")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| SharedString::from(v.to_string()));
+    log(LogEntry::info(format!(
+        "Connected ({} {})",
+        response.status().as_u16(),
+        response.status().canonical_reason().unwrap_or("")
+    )));
+    let _ = events.unbounded_send(SessionEvent::Open { protocol });
+
+    let (mut sink, mut stream) = socket.split();
+    let mut closing = false;
+    // Armed when our close frame is sent, so later frames don't restart it
+    let close_timeout = tokio::time::sleep(CLOSE_TIMEOUT);
+    tokio::pin!(close_timeout);
+    loop {
+        tokio::select! {
+            command = commands.next(), if !closing => {
+                let message = match command {
+                    Some(Command::Send(message)) => message,
+                    Some(Command::Close) | None => {
+                        closing = true;
+                        close_timeout.as_mut().reset(tokio::time::Instant::now() + CLOSE_TIMEOUT);
+                        Message::Close(Some(CloseFrame {
+                            code: CloseCode::Normal,
+                            reason: "".into(),
+                        }))
+                    }
+                };
+                let entry = LogEntry::frame(Direction::Sent, &message);
+                match sink.send(message).await {
+                    Ok(()) => log(entry),
+                    Err(e) => {
+                        log(LogEntry::info(format!("Send failed: {}", e)));
+                        break;
+                    }
+                }
+            }
+            message = stream.next() => match message {
+                // Pings are answered by the socket itself on the next read or write
+                Some(Ok(message)) => log(LogEntry::frame(Direction::Received, &message)),
+                Some(Err(e)) => {
+                    log(LogEntry::info(format!("Connection error: {}", e)));
+                    break;
+                }
+                None => break,
+            },
+            _ = &mut close_timeout, if closing => {
+                log(LogEntry::info("Server did not answer the close frame"));
+                break;
+            }
+        }
+    }
+
+    log(LogEntry::info("Disconnected"));
+}
+
+fn pretty_json(text: &str) -> Option<String> {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+        return None;
+    }
+    serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+}
+
+fn hex_preview(bytes: &[u8]) -> String {
+    let mut hex = bytes
+        .iter()
+        .take(HEX_PREVIEW_BYTES)
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if bytes.len() > HEX_PREVIEW_BYTES {
+        hex.push_str(" …");
+    }
+    format!("{} bytes {}", bytes.len(), hex).trim_end().to_string()
+}
+
+fn parse_hex(input: &str) -> Result<Vec<u8>, String> {
+    let digits: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.is_ascii() {
+        return Err("Hex input may only contain 0-9 and a-f".to_string());
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err("Hex input must have an even number of digits".to_string());
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| format!("Invalid hex byte \"{}\"", &digits[i..i + 2]))
+        })
+        .collect()
+}
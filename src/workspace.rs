@@ -1,15 +1,56 @@
-use crate::app_state::AppState;
+use crate::app_state::{AppState, ResponseEntry};
+use crate::charset::{self, TextEncoding};
+use crate::cookies::StoredCookie;
+use crate::decode::BinaryFormat;
+use crate::diff::{BodyDiff, Change, ChangeKind, Comparison, DiffLine, LineTag};
+use crate::download::{DownloadProgress, format_bytes, format_duration};
+use crate::grpc::GrpcCall;
+use crate::headers;
+use crate::hex::{self, BYTES_PER_LINE};
+use crate::image_info::{self, DecodedImage};
+use crate::highlight::{self, Highlights, Language};
+use crate::json_filter::{self, FilterOutput};
+use crate::json_tree::{self, JsonKind, JsonRow};
+use crate::markup::{BlockKind, PreviewBlock};
+use crate::media;
+use crate::ndjson::NdJsonRecord;
 use crate::response::{Response, ResponseContent};
+use crate::search::{self, Matcher, SearchHit, SearchOptions};
+use crate::sse::SseEvent;
 use crate::text_input::{TextInput, TextInputEvent};
 use crate::text_area::{TextArea, TextAreaEvent};
+use crate::text_lines::TextLines;
 use crate::theme::Theme;
+use crate::websocket::{Direction, SessionStatus, WebSocketSession};
+use gpui::prelude::*;
 use gpui::*;
-use std::sync::Arc;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::{Arc, Weak};
+use tempfile::TempPath;
+
+/// Zoom factor applied per zoom step in the image viewer.
+const IMAGE_ZOOM_STEP: f32 = 1.25;
+const MIN_IMAGE_ZOOM: f32 = 0.05;
+const MAX_IMAGE_ZOOM: f32 = 32.0;
+
+/// What the find bar searches. Rebuilt only when `search_version` moves.
+enum SearchSource {
+    /// The displayed body text, searched through `text_lines`.
+    Text(SharedString),
+    JsonRows(Vec<SharedString>),
+    Headers(Vec<SharedString>),
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ResponseTab {
     Body,
     Headers,
+    Redirects,
+    Timing,
+    Cookies,
+    Compare,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,12 +71,88 @@ pub struct Workspace {
     body_input: Entity<TextArea>,
     header_inputs: Vec<(Entity<TextInput>, Entity<TextInput>)>,
     query_inputs: Vec<(Entity<TextInput>, Entity<TextInput>)>,
+    environment_input: Entity<TextInput>,
+    memory_cap_input: Entity<TextInput>,
+    preview_size_input: Entity<TextInput>,
+
+    // Cookie jar editor
+    cookie_inputs: [Entity<TextInput>; 4],
+    editing_cookie: Option<StoredCookie>,
+
+    // WebSocket composer
+    message_input: Entity<TextArea>,
+    subprotocol_input: Entity<TextInput>,
+
+    // Last failure writing the response body to a file
+    file_error: Option<SharedString>,
+    // Bodies handed to external apps, removed when the workspace is dropped
+    opened_files: Vec<TempPath>,
+
+    // Event stream and WebSocket logs, following the newest entry
+    live_scroll: ScrollHandle,
+    followed_entries: usize,
+
+    // Line index of the displayed body text, rebuilt when the text changes
+    text_lines: Option<TextLines>,
+    text_list: ListState,
+    text_scroll: UniformListScrollHandle,
+    soft_wrap: bool,
+    html_preview: bool,
+    // Find in response
+    search_open: bool,
+    search_input: Entity<TextInput>,
+    search_options: SearchOptions,
+    search_source: Option<SearchSource>,
+    // Bumped whenever the searched view may have changed; the source is rebuilt once per bump
+    search_version: u64,
+    search_synced: Option<u64>,
+    search_response: Weak<Response>,
+    search_tab: ResponseTab,
+    search_hits: Vec<SearchHit>,
+    search_current: usize,
+    search_error: Option<SharedString>,
+    search_scroll: ScrollHandle,
+    encoding_menu_open: bool,
+    // Hex viewer
+    offset_input: Entity<TextInput>,
+    offset_error: Option<SharedString>,
+    hex_anchor: usize,
+    hex_selection: Option<Range<usize>>,
+    hex_scroll: UniformListScrollHandle,
+    // JSON tree viewer and filter
+    filter_input: Entity<TextInput>,
+    json_filter_output: Option<Result<FilterOutput, String>>,
+    json_raw: bool,
+    json_expanded: HashSet<String>,
+    json_rows: Vec<JsonRow>,
+    /// Document the rows were built from; a new response resets the expansion.
+    json_source: Option<Arc<Value>>,
+    json_scroll: UniformListScrollHandle,
+
+    // Response comparison, by `ResponseEntry::id`; unset picks the latest two
+    compare_base: Option<u64>,
+    compare_target: Option<u64>,
+    comparison: Option<((u64, u64), Comparison)>,
+    compare_scroll: UniformListScrollHandle,
+    snapshot_bar_open: bool,
+    snapshot_name_input: Entity<TextInput>,
+    headers_filter_input: Entity<TextInput>,
+
+    // Image viewer; zoom is `None` while the image fits the view
+    image_bytes: Option<Arc<[u8]>>,
+    image: Option<Result<DecodedImage, SharedString>>,
+    image_zoom: Option<f32>,
+    image_offset: Point<Pixels>,
+    image_drag: Option<Point<Pixels>>,
 }
 
 impl Workspace {
     pub fn new(state: Entity<AppState>, cx: &mut Context<Self>) -> Self {
         let app_state = state.read(cx);
         let url = app_state.url.clone();
+        let environment = app_state.environment.clone();
+        let max_in_memory_bytes = app_state.max_in_memory_bytes;
+        let preview_bytes = app_state.preview_bytes;
         
         let url_input = cx.new(|cx| {
             let mut input = TextInput::new(cx, "Enter URL...");
@@ -47,7 +164,11 @@ impl Workspace {
             TextInputEvent::EnterPressed => {
                 view.send_request(cx);
             }
-            _ => {}
+            TextInputEvent::TextChanged(text) => {
+                view.state.update(cx, |state, cx| {
+                    state.update_url(text.clone(), cx);
+                });
+            }
         })
         .detach();
 
@@ -63,6 +184,132 @@ impl Workspace {
             }
         }).detach();
 
+        let environment_input = cx.new(|cx| {
+            let mut input = TextInput::new(cx, "Environment");
+            input.set_text(environment.to_string(), cx);
+            input
+        });
+
+        cx.subscribe(&environment_input, |view, _input, event, cx| {
+            if let TextInputEvent::TextChanged(text) = event {
+                view.state.update(cx, |state, cx| {
+                    state.update_environment(text.clone(), cx);
+                });
+            }
+        })
+        .detach();
+
+        let memory_cap_input = cx.new(|cx| {
+            let mut input = TextInput::new(cx, "MB");
+            input.set_text((max_in_memory_bytes / (1024 * 1024)).to_string(), cx);
+            input
+        });
+
+        cx.subscribe(&memory_cap_input, |view, _input, event, cx| {
+            if let TextInputEvent::TextChanged(text) = event
+                && let Ok(megabytes) = text.trim().parse::<usize>()
+            {
+                view.state.update(cx, |state, cx| {
+                    state.update_max_in_memory_bytes(megabytes.max(1) * 1024 * 1024, cx);
+                });
+            }
+        })
+        .detach();
+
+        let preview_size_input = cx.new(|cx| {
+            let mut input = TextInput::new(cx, "KB");
+            input.set_text((preview_bytes / 1024).to_string(), cx);
+            input
+        });
+
+        cx.subscribe(&preview_size_input, |view, _input, event, cx| {
+            if let TextInputEvent::TextChanged(text) = event
+                && let Ok(kilobytes) = text.trim().parse::<usize>()
+            {
+                view.state.update(cx, |state, cx| {
+                    state.update_preview_bytes(kilobytes.max(1) * 1024, cx);
+                });
+            }
+        })
+        .detach();
+
+        let message_input = cx.new(|cx| TextArea::new(cx, "Message..."));
+
+        let subprotocol_input = cx.new(|cx| TextInput::new(cx, "Subprotocols (comma separated)"));
+
+        cx.subscribe(&subprotocol_input, |view, _input, event, cx| {
+            if let TextInputEvent::TextChanged(text) = event {
+                view.state.update(cx, |state, cx| {
+                    state.update_subprotocols(text.clone(), cx);
+                });
+            }
+        })
+        .detach();
+
+        let filter_input =
+            cx.new(|cx| TextInput::new(cx, "Filter: $.items[*].id or .items[].id"));
+
+        cx.subscribe(&filter_input, |view, _input, event, cx| {
+            if let TextInputEvent::TextChanged(text) = event {
+                view.state.update(cx, |state, cx| {
+                    state.update_json_filter(text.clone(), cx);
+                });
+                view.refresh_json_filter(cx);
+            }
+        })
+        .detach();
+
+        let search_input = cx.new(|cx| TextInput::new(cx, "Find in response"));
+
+        cx.subscribe(&search_input, |view, _input, event, cx| match event {
+            TextInputEvent::EnterPressed => view.next_search_hit(cx),
+            TextInputEvent::TextChanged(_) => {
+                view.search_current = 0;
+                view.run_search(cx);
+                view.scroll_to_search_hit();
+                cx.notify();
+            }
+        })
+        .detach();
+
+        let headers_filter_input = cx.new(|cx| TextInput::new(cx, "Filter headers"));
+
+        cx.subscribe(&headers_filter_input, |view, _input, event, cx| {
+            if let TextInputEvent::TextChanged(_) = event {
+                view.search_version += 1;
+                cx.notify();
+            }
+        })
+        .detach();
+
+        let snapshot_name_input = cx.new(|cx| TextInput::new(cx, "Snapshot name"));
+
+        cx.subscribe(&snapshot_name_input, |view, _input, event, cx| {
+            if let TextInputEvent::EnterPressed = event {
+                view.pin_snapshot(cx);
+            }
+        })
+        .detach();
+
+        let offset_input = cx.new(|cx| TextInput::new(cx, "Jump to offset (0x1f0 or 496)"));
+
+        cx.subscribe(&offset_input, |view, _input, event, cx| {
+            if let TextInputEvent::EnterPressed = event {
+                view.jump_to_offset(cx);
+            }
+        })
+        .detach();
+
+        let cookie_inputs = ["Name", "Value", "Domain", "Path"]
+            .map(|placeholder| cx.new(|cx| TextInput::new(cx, placeholder)));
+
+        // Files opened with external apps must not outlive the app
+        cx.on_app_quit(|view, _| {
+            view.opened_files.clear();
+            async {}
+        })
+        .detach();
+
         let mut workspace = Self {
             state,
             url_input,
@@ -72,6 +319,59 @@ impl Workspace {
             body_input,
             header_inputs: vec![],
             query_inputs: vec![],
+            environment_input,
+            memory_cap_input,
+            preview_size_input,
+            cookie_inputs,
+            editing_cookie: None,
+            message_input,
+            subprotocol_input,
+            file_error: None,
+            opened_files: Vec::new(),
+            live_scroll: ScrollHandle::new(),
+            followed_entries: 0,
+            text_lines: None,
+            text_list: ListState::new(0, ListAlignment::Top, px(200.)),
+            text_scroll: UniformListScrollHandle::new(),
+            soft_wrap: true,
+            html_preview: false,
+            search_open: false,
+            search_input,
+            search_options: SearchOptions::default(),
+            search_source: None,
+            search_version: 0,
+            search_synced: None,
+            search_response: Weak::new(),
+            search_tab: ResponseTab::Body,
+            search_hits: Vec::new(),
+            search_current: 0,
+            search_error: None,
+            search_scroll: ScrollHandle::new(),
+            encoding_menu_open: false,
+            offset_input,
+            offset_error: None,
+            hex_anchor: 0,
+            hex_selection: None,
+            hex_scroll: UniformListScrollHandle::new(),
+            filter_input,
+            json_filter_output: None,
+            json_raw: false,
+            json_expanded: HashSet::new(),
+            json_rows: Vec::new(),
+            json_source: None,
+            json_scroll: UniformListScrollHandle::new(),
+            compare_base: None,
+            compare_target: None,
+            comparison: None,
+            compare_scroll: UniformListScrollHandle::new(),
+            snapshot_bar_open: false,
+            snapshot_name_input,
+            headers_filter_input,
+            image_bytes: None,
+            image: None,
+            image_zoom: None,
+            image_offset: Point::default(),
+            image_drag: None,
         };
 
         // Add initial empty rows
@@ -133,6 +433,16 @@ impl Workspace {
             state.update_url(url, cx);
             state.send_request(cx);
         });
+        self.hex_selection = None;
+        self.offset_error = None;
+        self.file_error = None;
+    }
+
+    fn send_websocket_message(&mut self, cx: &mut Context<Self>) {
+        let message = self.message_input.read(cx).text();
+        self.state.update(cx, |state, cx| {
+            state.send_websocket_message(&message, cx);
+        });
     }
 
     fn render_sidebar(&self, cx: &mut Context<Self>) -> impl IntoElement {
@@ -143,6 +453,37 @@ impl Workspace {
             .bg(self.theme.sidebar_bg)
             .border_r_1()
             .border_color(self.theme.border)
+            .child(
+                div()
+                    .p_2()
+                    .flex_col()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(self.theme.border)
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::BOLD)
+                            .child("ENVIRONMENT"),
+                    )
+                    .child(self.environment_input.clone())
+                    .child(
+                        div()
+                            .pt_1()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child("Body memory cap (MB)"),
+                    )
+                    .child(self.memory_cap_input.clone())
+                    .child(
+                        div()
+                            .pt_1()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child("Spooled body preview (KB)"),
+                    )
+                    .child(self.preview_size_input.clone()),
+            )
             .child(
                 div()
                     .p_2()
@@ -170,6 +511,20 @@ impl Workspace {
     fn render_url_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let state = self.state.read(cx);
         let method = state.method.clone();
+        let connected = state.is_websocket()
+            && state.websocket.as_ref().is_some_and(WebSocketSession::is_active);
+        let calling = state.is_grpc() && state.grpc_call.as_ref().is_some_and(GrpcCall::is_running);
+        let send_label = if calling {
+            "Cancel"
+        } else if state.is_grpc() {
+            "Invoke"
+        } else if connected {
+            "Disconnect"
+        } else if state.is_websocket() {
+            "Connect"
+        } else {
+            "Send"
+        };
 
         div()
             .p_4()
@@ -194,6 +549,8 @@ impl Workspace {
                                 "POST" => "PUT",
                                 "PUT" => "DELETE",
                                 "DELETE" => "PATCH",
+                                "PATCH" => "WS",
+                                "WS" => "GRPC",
                                 _ => "GET",
                             };
                             state.update_method(next_method, cx);
@@ -211,14 +568,23 @@ impl Workspace {
                     .text_color(self.theme.accent_text)
                     .text_sm()
                     .cursor_pointer()
-                    .on_click(cx.listener(|view, _, _, cx| {
-                        view.send_request(cx);
+                    .on_click(cx.listener(move |view, _, _, cx| {
+                        if calling {
+                            view.state.update(cx, |state, cx| state.cancel_grpc_call(cx));
+                        } else if connected {
+                            view.state.update(cx, |state, cx| state.disconnect_websocket(cx));
+                        } else {
+                            view.send_request(cx);
+                        }
                     }))
-                    .child("Send"),
+                    .child(send_label),
             )
     }
 
     fn render_request_section(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let websocket = state.is_websocket();
+        let grpc = state.is_grpc();
         div()
             .flex_col()
             .border_b_1()
@@ -231,7 +597,11 @@ impl Workspace {
                     .pt_2()
                     .child(self.render_request_tab("Params", RequestTab::Params, cx))
                     .child(self.render_request_tab("Headers", RequestTab::Headers, cx))
-                    .child(self.render_request_tab("Body", RequestTab::Body, cx))
+                    .child(self.render_request_tab(
+                        if websocket || grpc { "Message" } else { "Body" },
+                        RequestTab::Body,
+                        cx,
+                    ))
             )
             .child(
                 div()
@@ -242,11 +612,169 @@ impl Workspace {
                     .child(match self.active_request_tab {
                         RequestTab::Params => self.render_key_value_editor(&self.query_inputs, "query", cx).into_any_element(),
                         RequestTab::Headers => self.render_key_value_editor(&self.header_inputs, "header", cx).into_any_element(),
+                        RequestTab::Body if grpc => self.render_grpc_composer(cx).into_any_element(),
+                        RequestTab::Body if websocket => self.render_message_composer(cx).into_any_element(),
                         RequestTab::Body => self.body_input.clone().into_any_element(),
                     })
             )
     }
 
+    fn render_message_composer(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let format = state.message_format;
+        let open = state
+            .websocket
+            .as_ref()
+            .is_some_and(|session| session.status == SessionStatus::Open);
+        let button = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .px_3()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .child(label)
+        };
+
+        div()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().flex_1().child(self.subprotocol_input.clone()))
+                    .child(button("message-format", format.label()).on_click(cx.listener(
+                        |view, _, _, cx| {
+                            view.state.update(cx, |state, cx| state.cycle_message_format(cx));
+                        },
+                    )))
+                    .child(
+                        button("send-ping", "Ping")
+                            .when(!open, |this| this.opacity(0.5))
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.state.update(cx, |state, cx| state.send_websocket_ping(cx));
+                            })),
+                    )
+                    .child(
+                        button("send-message", "Send message")
+                            .when(!open, |this| this.opacity(0.5))
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.send_websocket_message(cx);
+                            })),
+                    ),
+            )
+            .child(self.message_input.clone())
+    }
+
+    fn pick_proto_files(&mut self, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: true,
+            prompt: Some("Load".into()),
+        });
+        let state = self.state.clone();
+
+        cx.spawn(move |_view: WeakEntity<Workspace>, cx: &mut AsyncApp| {
+            let cx = cx.clone();
+            async move {
+                if let Ok(Ok(Some(paths))) = receiver.await {
+                    let _ = cx.update(|cx| {
+                        state.update(cx, |state, cx| state.load_proto_files(paths, cx))
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn select_grpc_method(&mut self, path: SharedString, cx: &mut Context<Self>) {
+        let template = self.state.update(cx, |state, cx| {
+            state.select_grpc_method(path, cx);
+            state.grpc_request_template()
+        });
+        // Start from the request message skeleton unless a message was already written
+        if let Some(template) = template
+            && self.body_input.read(cx).text().trim().is_empty()
+        {
+            self.body_input
+                .update(cx, |input, cx| input.set_text(template, cx));
+        }
+    }
+
+    fn render_grpc_composer(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let selected = state.grpc_method.clone();
+        let button = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .px_3()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .child(label)
+        };
+
+        div()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .items_center()
+                    .child(button("load-proto", "Load .proto files").on_click(cx.listener(
+                        |view, _, _, cx| view.pick_proto_files(cx),
+                    )))
+                    .child(button("grpc-reflection", "Server reflection").on_click(cx.listener(
+                        |view, _, _, cx| {
+                            view.state.update(cx, |state, cx| state.reflect_grpc_schema(cx));
+                        },
+                    )))
+                    .children(state.grpc_schema.as_ref().map(|schema| {
+                        div()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(schema.source.clone())
+                    }))
+                    .children(state.grpc_schema_status.clone().map(|status| {
+                        div().text_xs().text_color(self.theme.text_dim).child(status)
+                    })),
+            )
+            .children(state.grpc_schema.as_ref().map(|schema| {
+                div()
+                    .flex_col()
+                    .border_1()
+                    .border_color(self.theme.border)
+                    .children(schema.methods.iter().enumerate().map(|(i, method)| {
+                        let active = selected.as_ref() == Some(&method.path);
+                        let path = method.path.clone();
+                        div()
+                            .id(("grpc-method", i))
+                            .px_2()
+                            .py_1()
+                            .flex()
+                            .gap_2()
+                            .text_xs()
+                            .cursor_pointer()
+                            .when(active, |this| this.bg(self.theme.input_bg))
+                            .text_color(if active { self.theme.text } else { self.theme.text_dim })
+                            .on_click(cx.listener(move |view, _, _, cx| {
+                                view.select_grpc_method(path.clone(), cx);
+                            }))
+                            .child(div().flex_1().child(method.path.clone()))
+                            .child(method.kind())
+                    }))
+            }))
+            .child(self.body_input.clone())
+    }
+
     fn render_request_tab(&self, label: &'static str, tab: RequestTab, cx: &mut Context<Self>) -> impl IntoElement {
         let active = self.active_request_tab == tab;
         div()
@@ -315,7 +843,21 @@ impl Workspace {
         let state = self.state.read(cx);
         let response = state.response.clone();
 
+        if state.is_grpc()
+            && let Some(call) = &state.grpc_call
+        {
+            return self.render_grpc_call(call).into_any_element();
+        }
+        if state.is_websocket()
+            && let Some(session) = &state.websocket
+        {
+            return self.render_websocket_session(session).into_any_element();
+        }
+
         match response {
+            None if state.is_loading() => self
+                .render_progress(state.download.clone(), state.partial_body.clone(), cx)
+                .into_any_element(),
             None => div()
                 .flex_1()
                 .flex_col()
@@ -339,266 +881,3218 @@ impl Workspace {
                 div()
                     .flex_1()
                     .flex_col()
-                    .child(self.render_response_header(&response))
-                    .child(self.render_response_tabs(cx))
+                    .child(self.render_response_header(&response, cx))
+                    .when(
+                        self.snapshot_bar_open && !matches!(response.content, ResponseContent::Error(_)),
+                        |this| this.child(self.render_snapshot_bar(cx)),
+                    )
+                    .child(self.render_response_tabs(&response, cx))
+                    .when(
+                        self.search_open
+                            && matches!(active_tab, ResponseTab::Body | ResponseTab::Headers),
+                        |this| this.child(self.render_search_bar(cx)),
+                    )
                     .child(match active_tab {
                         ResponseTab::Body => {
-                            self.render_response_body(&response).into_any_element()
+                            self.render_response_body(&response, cx).into_any_element()
                         }
-                        ResponseTab::Headers => self.render_headers(&response).into_any_element(),
+                        ResponseTab::Headers => {
+                            self.render_headers(&response, cx).into_any_element()
+                        }
+                        ResponseTab::Redirects => {
+                            self.render_redirects(&response).into_any_element()
+                        }
+                        ResponseTab::Timing => self.render_timing(&response).into_any_element(),
+                        ResponseTab::Cookies => {
+                            self.render_cookies(&response, cx).into_any_element()
+                        }
+                        ResponseTab::Compare => self.render_compare(cx).into_any_element(),
                     })
                     .into_any_element()
             }
         }
     }
 
-    fn render_response_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
-        div()
-            .flex()
-            .gap_2()
-            .px_4()
-            .pt_2()
-            .border_b_1()
-            .border_color(self.theme.border)
-            .child(self.render_tab("Body", ResponseTab::Body, cx))
-            .child(self.render_tab("Headers", ResponseTab::Headers, cx))
-    }
-
-    fn render_tab(
-        &self,
-        label: &'static str,
-        tab: ResponseTab,
-        cx: &mut Context<Self>,
-    ) -> impl IntoElement {
-        let active = self.active_response_tab == tab;
-        div()
-            .id(label)
-            .px_3()
-            .py_1()
-            .text_xs()
-            .cursor_pointer()
-            .border_t_1()
-            .border_l_1()
-            .border_r_1()
-            .rounded_t_sm()
-            .border_color(if active {
-                self.theme.border
-            } else {
-                gpui::transparent_black()
-            })
-            .bg(if active {
-                self.theme.bg
-            } else {
-                gpui::transparent_black()
-            })
-            .text_color(if active {
-                self.theme.text
-            } else {
-                self.theme.text_dim
-            })
-            .on_click(cx.listener(move |view, _, _, cx| {
-                view.active_response_tab = tab;
-                cx.notify();
-            }))
-            .child(label)
-    }
+    fn render_websocket_session(&self, session: &WebSocketSession) -> impl IntoElement {
+        let session_color = match session.status {
+            SessionStatus::Open => status_color(200),
+            SessionStatus::Closed => status_color(500),
+            SessionStatus::Connecting | SessionStatus::Closing => status_color(300),
+        };
 
-    fn render_headers(&self, response: &Response) -> impl IntoElement {
         div()
-            .id("response-headers")
             .flex_1()
-            .p_4()
-            .overflow_y_scroll()
+            .flex_col()
             .child(
                 div()
-                    .flex_col()
-                    .gap_1()
-                    .children(response.headers.iter().map(|(k, v)| {
+                    .p_3()
+                    .border_b_1()
+                    .border_color(self.theme.border)
+                    .flex()
+                    .gap_4()
+                    .items_center()
+                    .child(
                         div()
-                            .flex()
-                            .gap_4()
-                            .py_1()
-                            .border_b_1()
-                            .border_color(rgb(0x2a2a2a))
-                            .child(
-                                div()
-                                    .w_48()
-                                    .text_xs()
+                            .text_sm()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(session_color)
+                            .child(session.status.label()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(session.url.clone()),
+                    )
+                    .children(session.protocol.clone().map(|protocol| {
+                        div()
+                            .text_xs()
+                            .px_2()
+                            .py_1()
+                            .bg(self.theme.input_bg)
+                            .border_1()
+                            .border_color(self.theme.input_border)
+                            .child(protocol)
+                    }))
+                    .child(div().flex_1())
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(format!("{} entries", session.log.len())),
+                    ),
+            )
+            .child(
+                div().flex_1().flex_col().p_4().child(
+                    div()
+                        .id("websocket-log")
+                        .size_full()
+                        .bg(self.theme.input_bg)
+                        .border_1()
+                        .border_color(self.theme.border)
+                        .overflow_y_scroll()
+                        .track_scroll(&self.live_scroll)
+                        .children(session.log.iter().map(|entry| {
+                            let (arrow, color) = match entry.direction {
+                                Direction::Sent => ("↑", self.theme.accent),
+                                Direction::Received => ("↓", status_color(200).into()),
+                                Direction::Info => ("•", self.theme.text_dim),
+                            };
+                            div()
+                                .p_2()
+                                .border_b_1()
+                                .border_color(self.theme.border)
+                                .flex()
+                                .gap_3()
+                                .child(
+                                    div()
+                                        .w_24()
+                                        .flex_shrink_0()
+                                        .text_xs()
+                                        .text_color(self.theme.text_dim)
+                                        .child(entry.at.clone()),
+                                )
+                                .child(
+                                    div()
+                                        .w_16()
+                                        .flex_shrink_0()
+                                        .text_xs()
+                                        .text_color(color)
+                                        .child(format!("{} {}", arrow, entry.kind)),
+                                )
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .font_family("monospace")
+                                        .text_sm()
+                                        .text_color(self.theme.text)
+                                        .child(entry.payload.clone()),
+                                )
+                        })),
+                ),
+            )
+    }
+
+    fn render_grpc_call(&self, call: &GrpcCall) -> impl IntoElement {
+        let (status_label, color) = match &call.status {
+            None => ("Calling...".to_string(), status_color(300)),
+            Some(status) => (
+                format!("{} {}", status.code, status.name),
+                status_color(if status.is_ok() { 200 } else { 500 }),
+            ),
+        };
+
+        div()
+            .flex_1()
+            .flex_col()
+            .child(
+                div()
+                    .p_3()
+                    .border_b_1()
+                    .border_color(self.theme.border)
+                    .flex()
+                    .gap_4()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(color)
+                            .child(status_label),
+                    )
+                    .children(
+                        call.status
+                            .as_ref()
+                            .filter(|status| !status.message.is_empty())
+                            .map(|status| {
+                                div()
+                                    .text_sm()
+                                    .text_color(self.theme.text_dim)
+                                    .child(status.message.clone())
+                            }),
+                    )
+                    .children(call.elapsed_ms.map(|elapsed_ms| {
+                        div()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(format!("Time: {}ms", elapsed_ms))
+                    }))
+                    .child(div().flex_1())
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(format!("{} · {} messages", call.path, call.messages.len())),
+                    ),
+            )
+            .child(
+                div()
+                    .id("grpc-call")
+                    .flex_1()
+                    .flex_col()
+                    .p_4()
+                    .gap_3()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.live_scroll)
+                    .children(call.messages.iter().map(|message| {
+                        div()
+                            .bg(self.theme.input_bg)
+                            .border_1()
+                            .border_color(self.theme.border)
+                            .p_3()
+                            .font_family("monospace")
+                            .text_sm()
+                            .text_color(self.theme.text)
+                            .child(message.clone())
+                    }))
+                    .child(self.render_metadata("HEADERS", &call.headers))
+                    .child(self.render_metadata("TRAILERS", &call.trailers)),
+            )
+    }
+
+    fn render_metadata(
+        &self,
+        title: &'static str,
+        pairs: &[(SharedString, SharedString)],
+    ) -> impl IntoElement {
+        div()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(self.theme.text_dim)
+                    .child(title),
+            )
+            .children(pairs.iter().map(|(k, v)| {
+                div()
+                    .flex()
+                    .gap_4()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .child(
+                        div()
+                            .w_48()
+                            .text_xs()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(self.theme.text_dim)
+                            .child(k.clone()),
+                    )
+                    .child(div().flex_1().text_xs().text_color(self.theme.text).child(v.clone()))
+            }))
+    }
+
+    fn render_response_tabs(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let redirects_label: SharedString = if response.redirects.is_empty() {
+            "Redirects".into()
+        } else {
+            format!("Redirects ({})", response.redirects.len()).into()
+        };
+
+        div()
+            .flex()
+            .gap_2()
+            .px_4()
+            .pt_2()
+            .border_b_1()
+            .border_color(self.theme.border)
+            .child(self.render_tab("Body", ResponseTab::Body, cx))
+            .child(self.render_tab("Headers", ResponseTab::Headers, cx))
+            .child(self.render_tab(redirects_label, ResponseTab::Redirects, cx))
+            .child(self.render_tab("Timing", ResponseTab::Timing, cx))
+            .child(self.render_tab("Cookies", ResponseTab::Cookies, cx))
+            .child(self.render_tab("Compare", ResponseTab::Compare, cx))
+    }
+
+    fn render_tab(
+        &self,
+        label: impl Into<SharedString>,
+        tab: ResponseTab,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let label = label.into();
+        let active = self.active_response_tab == tab;
+        div()
+            .id(ElementId::Name(label.clone()))
+            .px_3()
+            .py_1()
+            .text_xs()
+            .cursor_pointer()
+            .border_t_1()
+            .border_l_1()
+            .border_r_1()
+            .rounded_t_sm()
+            .border_color(if active {
+                self.theme.border
+            } else {
+                gpui::transparent_black()
+            })
+            .bg(if active {
+                self.theme.bg
+            } else {
+                gpui::transparent_black()
+            })
+            .text_color(if active {
+                self.theme.text
+            } else {
+                self.theme.text_dim
+            })
+            .on_click(cx.listener(move |view, _, _, cx| {
+                view.active_response_tab = tab;
+                cx.notify();
+            }))
+            .child(label)
+    }
+
+    /// Response headers matching the header filter, in received order.
+    fn visible_headers(&self, response: &Response, cx: &App) -> Vec<(SharedString, SharedString)> {
+        let filter = self.headers_filter_input.read(cx).text().to_lowercase();
+        response
+            .headers
+            .iter()
+            .filter(|(name, value)| {
+                filter.is_empty()
+                    || name.to_lowercase().contains(&filter)
+                    || value.to_lowercase().contains(&filter)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn render_headers(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let headers = self.visible_headers(response, cx);
+        let all: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}\n", name, value))
+            .collect();
+        let button = |id: ElementId, label: &'static str| {
+            div()
+                .id(id)
+                .flex_none()
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .child(label)
+        };
+
+        let toolbar = div()
+            .flex()
+            .gap_2()
+            .items_center()
+            .child(div().flex_1().child(self.headers_filter_input.clone()))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(self.theme.text_dim)
+                    .child(format!("{} of {}", headers.len(), response.headers.len())),
+            )
+            .child(button("copy-all-headers".into(), "Copy all").on_click(cx.listener(
+                move |_, _, _, cx| cx.write_to_clipboard(ClipboardItem::new_string(all.clone())),
+            )));
+
+        let rows = headers.into_iter().enumerate().map(|(i, (k, v))| {
+            // Hits are found in "name: value"
+            let value_offset = k.len() + 2;
+            let fields = headers::decode(&k, &v);
+            let line: SharedString = format!("{}: {}", k, v).into();
+            div()
+                .flex_col()
+                .py_1()
+                .border_b_1()
+                .border_color(rgb(0x2a2a2a))
+                .child(
+                    div()
+                        .flex()
+                        .gap_4()
+                        .items_start()
+                        .child(
+                            div()
+                                .id(("header-name", i))
+                                .w_48()
+                                .flex_none()
+                                .text_xs()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(self.theme.text_dim)
+                                .when_some(headers::description(&k), |this, description| {
+                                    this.tooltip(move |_window, cx| {
+                                        cx.new(|_| TextTooltip(description.into())).into()
+                                    })
+                                })
+                                .child(
+                                    StyledText::new(k.clone())
+                                        .with_highlights(self.hit_highlights(i, 0, k.len())),
+                                ),
+                        )
+                        .child(
+                            div()
+                                .flex_1()
+                                .min_w_0()
+                                .text_xs()
+                                .text_color(self.theme.text)
+                                .child(
+                                    StyledText::new(v.clone())
+                                        .with_highlights(self.hit_highlights(i, value_offset, v.len())),
+                                ),
+                        )
+                        .child(button(("copy-header", i).into(), "Copy").on_click(cx.listener(
+                            move |_, _, _, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(line.to_string()))
+                            },
+                        ))),
+                )
+                .when(!fields.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .pl(px(208.))
+                            .pt_1()
+                            .flex_col()
+                            .children(fields.into_iter().map(|field| {
+                                div()
+                                    .flex()
+                                    .gap_2()
+                                    .text_xs()
+                                    .child(
+                                        div()
+                                            .w_32()
+                                            .flex_none()
+                                            .text_color(self.theme.syntax_key)
+                                            .child(field.label),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .min_w_0()
+                                            .text_color(self.theme.text_dim)
+                                            .child(field.value),
+                                    )
+                            })),
+                    )
+                })
+        });
+
+        div()
+            .flex_1()
+            .p_4()
+            .flex_col()
+            .gap_2()
+            .child(toolbar)
+            .child(
+                div()
+                    .id("response-headers")
+                    .flex_1()
+                    .flex_col()
+                    .gap_1()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.search_scroll)
+                    .children(rows),
+            )
+    }
+
+    fn render_redirects(&self, response: &Response) -> impl IntoElement {
+        div()
+            .id("response-redirects")
+            .flex_1()
+            .p_4()
+            .overflow_y_scroll()
+            .child(if response.redirects.is_empty() {
+                div()
+                    .text_xs()
+                    .text_color(self.theme.text_dim)
+                    .child("No redirects were followed.")
+            } else {
+                div()
+                    .flex_col()
+                    .gap_3()
+                    .children(response.redirects.iter().enumerate().map(|(i, hop)| {
+                        div()
+                            .flex_col()
+                            .gap_1()
+                            .pb_2()
+                            .border_b_1()
+                            .border_color(rgb(0x2a2a2a))
+                            .child(
+                                div()
+                                    .flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .text_xs()
+                                    .child(
+                                        div()
+                                            .text_color(self.theme.text_dim)
+                                            .child(format!("#{}", i + 1)),
+                                    )
+                                    .child(
+                                        div()
+                                            .font_weight(FontWeight::BOLD)
+                                            .text_color(status_color(hop.status))
+                                            .child(format!("{}", hop.status)),
+                                    )
+                                    .child(div().child(hop.url.clone()))
+                                    .child(
+                                        div()
+                                            .text_color(self.theme.text_dim)
+                                            .child(format!("{}ms", hop.elapsed_ms)),
+                                    ),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(self.theme.accent)
+                                    .child(format!("→ {}", hop.location)),
+                            )
+                            .children(hop.headers.iter().map(|(k, v)| {
+                                div()
+                                    .flex()
+                                    .gap_4()
+                                    .pl_4()
+                                    .child(
+                                        div()
+                                            .w_48()
+                                            .text_xs()
+                                            .text_color(self.theme.text_dim)
+                                            .child(k.clone()),
+                                    )
+                                    .child(div().flex_1().text_xs().child(v.clone()))
+                            }))
+                    }))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .text_xs()
+                            .child(
+                                div()
                                     .font_weight(FontWeight::BOLD)
+                                    .text_color(status_color(response.status))
+                                    .child(format!("{}", response.status)),
+                            )
+                            .child(
+                                div()
+                                    .text_color(self.theme.text_dim)
+                                    .child(format!("Final response after {}ms", response.elapsed_ms)),
+                            ),
+                    )
+            })
+    }
+
+    fn render_timing(&self, response: &Response) -> impl IntoElement {
+        let timing = &response.timing;
+        let total = timing.total_ms().max(1.0);
+        let info_row = |label: &'static str, value: SharedString| {
+            div()
+                .flex()
+                .gap_4()
+                .py_1()
+                .child(
+                    div()
+                        .w_40()
+                        .text_xs()
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(self.theme.text_dim)
+                        .child(label),
+                )
+                .child(div().flex_1().text_xs().child(value))
+        };
+
+        div()
+            .id("response-timing")
+            .flex_1()
+            .p_4()
+            .overflow_y_scroll()
+            .child(
+                div()
+                    .flex_col()
+                    .gap_2()
+                    .children(timing.phases().into_iter().map(|(label, offset, duration)| {
+                        div()
+                            .flex()
+                            .gap_4()
+                            .items_center()
+                            .child(
+                                div()
+                                    .w_40()
+                                    .text_xs()
                                     .text_color(self.theme.text_dim)
-                                    .child(k.clone()),
+                                    .child(label),
+                            )
+                            .child(
+                                div().flex_1().h_3().relative().child(
+                                    div()
+                                        .absolute()
+                                        .top_0()
+                                        .h_full()
+                                        .min_w(px(2.))
+                                        .left(relative((offset / total) as f32))
+                                        .w(relative((duration / total) as f32))
+                                        .bg(self.theme.accent),
+                                ),
+                            )
+                            .child(
+                                div()
+                                    .w_20()
+                                    .text_xs()
+                                    .child(format!("{:.1}ms", duration)),
+                            )
+                    }))
+                    .child(
+                        div()
+                            .pt_2()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(format!("Total: {:.1}ms", timing.total_ms())),
+                    ),
+            )
+            .child(
+                div()
+                    .mt_4()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(self.theme.border)
+                    .flex_col()
+                    .child(info_row(
+                        "Remote address",
+                        timing.remote_addr.clone().unwrap_or("Unknown".into()),
+                    ))
+                    .child(info_row("HTTP version", timing.http_version.clone()))
+                    .children(timing.tls.as_ref().map(|tls| {
+                        div()
+                            .flex_col()
+                            .child(info_row("TLS subject", tls.subject.clone()))
+                            .child(info_row("TLS issuer", tls.issuer.clone()))
+                            .child(info_row("TLS expires", tls.not_after.clone()))
+                    })),
+            )
+    }
+
+    fn edit_cookie(&mut self, cookie: Option<StoredCookie>, cx: &mut Context<Self>) {
+        let values = match &cookie {
+            Some(cookie) => [
+                cookie.name.clone(),
+                cookie.value.clone(),
+                cookie.domain.clone(),
+                cookie.path.clone(),
+            ],
+            None => Default::default(),
+        };
+        for (input, value) in self.cookie_inputs.iter().zip(values) {
+            input.update(cx, |input, cx| input.set_text(value, cx));
+        }
+        self.editing_cookie = cookie;
+        cx.notify();
+    }
+
+    fn save_cookie(&mut self, cx: &mut Context<Self>) {
+        let [name, value, domain, path] =
+            self.cookie_inputs.each_ref().map(|input| input.read(cx).text().trim().to_string());
+        if name.is_empty() || domain.is_empty() {
+            return;
+        }
+
+        let previous = self.editing_cookie.take();
+        let cookie = StoredCookie {
+            name,
+            value,
+            domain: domain.trim_start_matches('.').to_ascii_lowercase(),
+            path: if path.starts_with('/') { path } else { "/".to_string() },
+            expires: previous.as_ref().and_then(|c| c.expires),
+            secure: previous.as_ref().is_some_and(|c| c.secure),
+            http_only: previous.as_ref().is_some_and(|c| c.http_only),
+            host_only: previous.as_ref().is_none_or(|c| c.host_only),
+        };
+        self.state.update(cx, |state, cx| {
+            if let Some(previous) = &previous {
+                state.remove_cookie(previous, cx);
+            }
+            state.upsert_cookie(cookie, cx);
+        });
+        self.edit_cookie(None, cx);
+    }
+
+    fn render_cookie_row(&self, cookie: &StoredCookie) -> Div {
+        let mut flags = vec![];
+        if cookie.secure {
+            flags.push("Secure");
+        }
+        if cookie.http_only {
+            flags.push("HttpOnly");
+        }
+
+        div()
+            .flex()
+            .flex_1()
+            .gap_4()
+            .text_xs()
+            .child(
+                div()
+                    .w_48()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(self.theme.text_dim)
+                    .child(cookie.name.clone()),
+            )
+            .child(div().flex_1().child(cookie.value.clone()))
+            .child(div().w_32().child(cookie.path.clone()))
+            .child(div().w_48().child(cookie.expires_label()))
+            .child(
+                div()
+                    .w_24()
+                    .text_color(self.theme.text_dim)
+                    .child(flags.join(" ")),
+            )
+    }
+
+    fn render_cookies(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let environment = state.environment.clone();
+        let jar = state.cookies();
+
+        let mut domains: Vec<(String, Vec<StoredCookie>)> = vec![];
+        for cookie in jar {
+            match domains.last_mut() {
+                Some((domain, cookies)) if *domain == cookie.domain => cookies.push(cookie),
+                _ => domains.push((cookie.domain.clone(), vec![cookie])),
+            }
+        }
+
+        let section_title = |title: String| {
+            div()
+                .text_sm()
+                .font_weight(FontWeight::BOLD)
+                .child(title)
+        };
+        let button = |id: ElementId, label: &'static str| {
+            div()
+                .id(id)
+                .px_2()
+                .text_xs()
+                .text_color(self.theme.accent)
+                .cursor_pointer()
+                .child(label)
+        };
+
+        div()
+            .id("response-cookies")
+            .flex_1()
+            .p_4()
+            .flex_col()
+            .gap_4()
+            .overflow_y_scroll()
+            .child(
+                div()
+                    .flex_col()
+                    .gap_1()
+                    .child(section_title(format!(
+                        "Set by this response ({})",
+                        response.cookies.len()
+                    )))
+                    .children(response.cookies.iter().map(|cookie| {
+                        div()
+                            .flex()
+                            .py_1()
+                            .border_b_1()
+                            .border_color(rgb(0x2a2a2a))
+                            .child(self.render_cookie_row(cookie))
+                            .child(div().w_24().text_xs().child(cookie.domain.clone()))
+                    })),
+            )
+            .child(
+                div()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex()
+                            .justify_between()
+                            .child(section_title(format!("Cookie jar ({})", environment)))
+                            .child(button("cookies-clear-all".into(), "Clear all").on_click(
+                                cx.listener(|view, _, _, cx| {
+                                    view.state.update(cx, |state, cx| state.clear_cookies(cx));
+                                }),
+                            )),
+                    )
+                    .children(domains.into_iter().enumerate().map(|(i, (domain, cookies))| {
+                        let clear_domain = domain.clone();
+                        div()
+                            .flex_col()
+                            .child(
+                                div()
+                                    .flex()
+                                    .justify_between()
+                                    .pt_2()
+                                    .text_xs()
+                                    .text_color(self.theme.accent)
+                                    .child(domain)
+                                    .child(
+                                        button(("cookies-clear-domain", i).into(), "Clear domain")
+                                            .on_click(cx.listener(move |view, _, _, cx| {
+                                                view.state.update(cx, |state, cx| {
+                                                    state.clear_cookie_domain(&clear_domain, cx)
+                                                });
+                                            })),
+                                    ),
+                            )
+                            .children(cookies.into_iter().enumerate().map(|(j, cookie)| {
+                                let edit = cookie.clone();
+                                let remove = cookie.clone();
+                                let id = i * 10_000 + j;
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .py_1()
+                                    .border_b_1()
+                                    .border_color(rgb(0x2a2a2a))
+                                    .child(self.render_cookie_row(&cookie))
+                                    .child(button(("cookie-edit", id).into(), "Edit").on_click(
+                                        cx.listener(move |view, _, _, cx| {
+                                            view.edit_cookie(Some(edit.clone()), cx);
+                                        }),
+                                    ))
+                                    .child(button(("cookie-remove", id).into(), "✕").on_click(
+                                        cx.listener(move |view, _, _, cx| {
+                                            view.state.update(cx, |state, cx| {
+                                                state.remove_cookie(&remove, cx)
+                                            });
+                                        }),
+                                    ))
+                            }))
+                    })),
+            )
+            .child(
+                div()
+                    .flex_col()
+                    .gap_2()
+                    .child(section_title(if self.editing_cookie.is_some() {
+                        "Edit cookie".to_string()
+                    } else {
+                        "Add cookie".to_string()
+                    }))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .children(
+                                self.cookie_inputs
+                                    .iter()
+                                    .map(|input| div().flex_1().child(input.clone())),
+                            )
+                            .child(button("cookie-save".into(), "Save").on_click(cx.listener(
+                                |view, _, _, cx| {
+                                    view.save_cookie(cx);
+                                },
+                            )))
+                            .child(button("cookie-new".into(), "New").on_click(cx.listener(
+                                |view, _, _, cx| {
+                                    view.edit_cookie(None, cx);
+                                },
+                            ))),
+                    ),
+            )
+    }
+
+    fn render_progress(
+        &self,
+        progress: Option<DownloadProgress>,
+        partial_body: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let status = match &progress {
+            None => "Waiting for response...".to_string(),
+            Some(progress) => {
+                let mut status = format!("Downloading {}", format_bytes(progress.received));
+                if let Some(total) = progress.total {
+                    status.push_str(&format!(" of {}", format_bytes(total)));
+                }
+                status.push_str(&format!(" · {}/s", format_bytes(progress.rate() as u64)));
+                if let Some(eta) = progress.eta() {
+                    status.push_str(&format!(" · ETA {}", format_duration(eta)));
+                }
+                status
+            }
+        };
+        let fraction = progress.as_ref().and_then(DownloadProgress::fraction);
+
+        div()
+            .flex_1()
+            .flex_col()
+            .p_4()
+            .child(
+                div()
+                    .flex_1()
+                    .bg(self.theme.bg)
+                    .border_1()
+                    .border_color(self.theme.border)
+                    .p_4()
+                    .flex_col()
+                    .gap_3()
+                    .items_center()
+                    .justify_center()
+                    .child(div().text_sm().text_color(self.theme.text_dim).child(status))
+                    .children(fraction.map(|fraction| {
+                        div().w_64().h_2().bg(self.theme.input_bg).child(
+                            div()
+                                .h_full()
+                                .w(relative(fraction))
+                                .bg(self.theme.accent),
+                        )
+                    }))
+                    .child(
+                        div()
+                            .id("cancel-request")
+                            .px_3()
+                            .py_1()
+                            .text_xs()
+                            .border_1()
+                            .border_color(self.theme.input_border)
+                            .cursor_pointer()
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.state.update(cx, |state, cx| state.cancel_request(cx));
+                            }))
+                            .child("Cancel"),
+                    )
+                    .children(partial_body.map(|text| {
+                        div()
+                            .id("partial-body")
+                            .w_full()
+                            .flex_1()
+                            .bg(self.theme.input_bg)
+                            .border_1()
+                            .border_color(self.theme.border)
+                            .p_3()
+                            .font_family("monospace")
+                            .text_sm()
+                            .text_color(self.theme.text)
+                            .overflow_y_scroll()
+                            .track_scroll(&self.live_scroll)
+                            .child(text)
+                    })),
+            )
+    }
+
+    fn save_response(&mut self, cx: &mut Context<Self>) {
+        let state = self.state.read(cx);
+        let Some(response) = state.response.clone() else {
+            return;
+        };
+        let suggested_name = reqwest::Url::parse(&state.url)
+            .ok()
+            .and_then(|url| {
+                url.path_segments()
+                    .and_then(|mut segments| segments.next_back().map(str::to_string))
+            })
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "response".to_string());
+        let directory = dirs::download_dir().unwrap_or_else(std::env::temp_dir);
+        let receiver = cx.prompt_for_new_path(&directory, Some(&suggested_name));
+        self.file_error = None;
+
+        cx.spawn(move |view: WeakEntity<Workspace>, cx: &mut AsyncApp| {
+            let cx = cx.clone();
+            async move {
+                let Ok(Ok(Some(path))) = receiver.await else {
+                    return;
+                };
+                let result = cx
+                    .background_executor()
+                    .spawn(async move {
+                        response.body.save_to(&path).map_err(|e| {
+                            format!("Failed to save response to {}: {}", path.display(), e)
+                        })
+                    })
+                    .await;
+                let _ = cx.update(|cx| {
+                    view.update(cx, |view, cx| {
+                        view.file_error = result.err().map(Into::into);
+                        cx.notify();
+                    })
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Pinned snapshots of the request, then recent responses.
+    fn compare_candidates(&self, cx: &App) -> Vec<ResponseEntry> {
+        let state = self.state.read(cx);
+        state.pinned.iter().chain(&state.responses).cloned().collect()
+    }
+
+    /// The two candidates being compared, as (base, target).
+    fn compare_pair(&self, cx: &App) -> Option<(ResponseEntry, ResponseEntry)> {
+        let responses = self.compare_candidates(cx);
+        let find = |id: Option<u64>| id.and_then(|id| responses.iter().position(|e| e.id == id));
+        let target = find(self.compare_target).or(responses.len().checked_sub(1))?;
+        let base = find(self.compare_base)
+            .or(target.checked_sub(1))
+            .or((responses.len() > 1).then(|| responses.len() - 1))?;
+        Some((responses[base].clone(), responses[target].clone()))
+    }
+
+    /// Diffs the selected pair when the comparison is shown and the pair changed.
+    fn sync_comparison(&mut self, cx: &mut Context<Self>) {
+        if self.active_response_tab != ResponseTab::Compare {
+            return;
+        }
+        let Some((base, target)) = self.compare_pair(cx) else {
+            self.comparison = None;
+            return;
+        };
+        let ids = (base.id, target.id);
+        if self.comparison.as_ref().is_some_and(|(cached, _)| *cached == ids) {
+            return;
+        }
+        self.comparison = Some((ids, Comparison::new(&base.response, &target.response)));
+        self.compare_scroll.scroll_to_item(0, ScrollStrategy::Top);
+    }
+
+    fn render_compare(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (Some((base, target)), Some((_, comparison))) = (self.compare_pair(cx), &self.comparison)
+        else {
+            return div()
+                .p_4()
+                .text_sm()
+                .text_color(self.theme.text_dim)
+                .child("Send the request again or pin a snapshot to compare responses.")
+                .into_any_element();
+        };
+        let entries = self.compare_candidates(cx);
+
+        let pickers = div()
+            .flex_col()
+            .gap_1()
+            .child(self.render_compare_picker("Base", true, &entries, &base, cx))
+            .child(self.render_compare_picker("Compare", false, &entries, &target, cx))
+            .child(
+                div()
+                    .id("compare-swap")
+                    .w_16()
+                    .px_2()
+                    .py_1()
+                    .text_xs()
+                    .border_1()
+                    .border_color(self.theme.input_border)
+                    .cursor_pointer()
+                    .child("Swap")
+                    .on_click(cx.listener(move |view, _, _, cx| {
+                        view.compare_base = Some(target.id);
+                        view.compare_target = Some(base.id);
+                        cx.notify();
+                    })),
+            );
+
+        let headers = div()
+            .flex_col()
+            .child(self.render_compare_title(if comparison.headers.is_empty() {
+                "Headers are identical".to_string()
+            } else {
+                format!("Headers: {} changed", comparison.headers.len())
+            }))
+            .child(
+                div()
+                    .id("compare-headers")
+                    .max_h_40()
+                    .overflow_y_scroll()
+                    .children(comparison.headers.iter().map(|change| self.render_change(change))),
+            );
+
+        let (body_title, body) = match &comparison.body {
+            BodyDiff::Json(changes) => (
+                if changes.is_empty() {
+                    "JSON bodies are identical".to_string()
+                } else {
+                    format!("JSON body: {} paths differ", changes.len())
+                },
+                uniform_list(
+                    "compare-json",
+                    changes.len(),
+                    cx.processor(|view, range: Range<usize>, _window, _cx| {
+                        let Some((_, Comparison { body: BodyDiff::Json(changes), .. })) =
+                            &view.comparison
+                        else {
+                            return Vec::new();
+                        };
+                        range.map(|ix| view.render_change(&changes[ix])).collect()
+                    }),
+                )
+                .size_full()
+                .track_scroll(self.compare_scroll.clone())
+                .into_any_element(),
+            ),
+            BodyDiff::Lines(lines) => {
+                let added = lines.iter().filter(|line| line.tag == LineTag::Added).count();
+                let removed = lines.iter().filter(|line| line.tag == LineTag::Removed).count();
+                (
+                    if added + removed == 0 {
+                        "Bodies are identical".to_string()
+                    } else {
+                        format!("Body: {} lines added, {} removed", added, removed)
+                    },
+                    uniform_list(
+                        "compare-lines",
+                        lines.len(),
+                        cx.processor(|view, range: Range<usize>, _window, _cx| {
+                            let Some((_, Comparison { body: BodyDiff::Lines(lines), .. })) =
+                                &view.comparison
+                            else {
+                                return Vec::new();
+                            };
+                            range.map(|ix| view.render_diff_line(&lines[ix])).collect()
+                        }),
+                    )
+                    .size_full()
+                    .track_scroll(self.compare_scroll.clone())
+                    .into_any_element(),
+                )
+            }
+            BodyDiff::Bytes { identical } => (
+                if *identical {
+                    "Bodies are byte-for-byte identical".to_string()
+                } else {
+                    "Bodies differ".to_string()
+                },
+                div().into_any_element(),
+            ),
+        };
+
+        div()
+            .flex_1()
+            .flex_col()
+            .gap_3()
+            .p_4()
+            .child(pickers)
+            .child(self.render_compare_metrics(&base.response, &target.response))
+            .child(headers)
+            .child(self.render_compare_title(body_title))
+            .child(
+                div()
+                    .flex_1()
+                    .bg(self.theme.input_bg)
+                    .border_1()
+                    .border_color(self.theme.border)
+                    .font_family("monospace")
+                    .text_xs()
+                    .child(body),
+            )
+            .into_any_element()
+    }
+
+    fn render_compare_picker(
+        &self,
+        label: &'static str,
+        is_base: bool,
+        entries: &[ResponseEntry],
+        selected: &ResponseEntry,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let id_prefix = if is_base { "compare-base" } else { "compare-target" };
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_1()
+            .items_center()
+            .child(div().w_16().text_xs().text_color(self.theme.text_dim).child(label))
+            .children(entries.iter().rev().map(|entry| {
+                let id = entry.id;
+                div()
+                    .id((id_prefix, id as usize))
+                    .px_2()
+                    .py_1()
+                    .text_xs()
+                    .border_1()
+                    .border_color(self.theme.input_border)
+                    .cursor_pointer()
+                    .when(id == selected.id, |this| {
+                        this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                    })
+                    .child(match &entry.snapshot {
+                        Some(snapshot) => format!("{} (pinned)", snapshot.name),
+                        None => format!("#{} {}", id, entry.response.status),
+                    })
+                    .on_click(cx.listener(move |view, _, _, cx| {
+                        if is_base {
+                            view.compare_base = Some(id);
+                        } else {
+                            view.compare_target = Some(id);
+                        }
+                        cx.notify();
+                    }))
+            }))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(self.theme.text_dim)
+                    .child(match &selected.snapshot {
+                        Some(snapshot) => format!("Snapshot \"{}\" of {}", snapshot.name, selected.request),
+                        None => selected.request.to_string(),
+                    }),
+            )
+    }
+
+    fn open_snapshot_bar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.snapshot_bar_open = !self.snapshot_bar_open;
+        if self.snapshot_bar_open {
+            let name = format!("Snapshot {}", self.state.read(cx).pinned.len() + 1);
+            self.snapshot_name_input
+                .update(cx, |input, cx| input.set_text(name, cx));
+            window.focus(&self.snapshot_name_input.focus_handle(cx));
+        }
+        cx.notify();
+    }
+
+    fn pin_snapshot(&mut self, cx: &mut Context<Self>) {
+        let name = self.snapshot_name_input.read(cx).text().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        self.state.update(cx, |state, cx| state.pin_snapshot(name, cx));
+        self.snapshot_bar_open = false;
+        cx.notify();
+    }
+
+    /// Names and pins the current response, and lists the request's snapshots.
+    fn render_snapshot_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let pinned: Vec<ResponseEntry> = state.pinned.clone();
+        let error = state.snapshot_error.clone();
+        let button = |id: ElementId, label: &'static str| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .child(label)
+        };
+
+        div()
+            .px_3()
+            .py_2()
+            .border_b_1()
+            .border_color(self.theme.border)
+            .flex()
+            .flex_wrap()
+            .gap_2()
+            .items_center()
+            .child(div().w_64().child(self.snapshot_name_input.clone()))
+            .child(
+                button("pin-snapshot".into(), "Pin")
+                    .on_click(cx.listener(|view, _, _, cx| view.pin_snapshot(cx))),
+            )
+            .when_some(error, |this, error| {
+                this.child(div().text_xs().text_color(status_color(500)).child(error))
+            })
+            .children(pinned.into_iter().filter_map(|entry| {
+                let snapshot = entry.snapshot?;
+                Some(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_1()
+                        .text_xs()
+                        .text_color(self.theme.text_dim)
+                        .child(snapshot.name.clone())
+                        .child(
+                            button(ElementId::Name(format!("delete-snapshot-{}", snapshot.id).into()), "×")
+                                .on_click(cx.listener(move |view, _, _, cx| {
+                                    view.state.update(cx, |state, cx| {
+                                        state.delete_snapshot(&snapshot, cx)
+                                    });
+                                })),
+                        ),
+                )
+            }))
+    }
+
+    fn render_compare_title(&self, title: String) -> impl IntoElement {
+        div()
+            .text_xs()
+            .font_weight(FontWeight::BOLD)
+            .text_color(self.theme.text_dim)
+            .child(title)
+    }
+
+    /// Status, size and timing of both responses side by side, with the change.
+    fn render_compare_metrics(&self, base: &Response, target: &Response) -> impl IntoElement {
+        let row = |label: SharedString, left: String, right: String, delta: Option<(String, Rgba)>| {
+            let cell = |text: String| div().w_32().text_xs().child(text);
+            div()
+                .flex()
+                .gap_4()
+                .child(
+                    div()
+                        .w_40()
+                        .text_xs()
+                        .text_color(self.theme.text_dim)
+                        .child(label),
+                )
+                .child(cell(left))
+                .child(cell(right))
+                .when_some(delta, |this, (delta, color)| {
+                    this.child(div().text_xs().text_color(color).child(delta))
+                })
+        };
+        // Slower and bigger are shown as regressions
+        let ms_delta = |left: f64, right: f64| {
+            let diff = right - left;
+            (diff.abs() >= 0.1).then(|| {
+                let color = if diff > 0.0 { status_color(500) } else { status_color(200) };
+                (format!("{:+.1}ms", diff), color)
+            })
+        };
+        let ms = |value: Option<f64>| value.map_or("-".to_string(), |ms| format!("{:.1}ms", ms));
+
+        let status = |response: &Response| format!("{} {}", response.status, response.status_text);
+        let base_phases = base.timing.phases();
+        let target_phases = target.timing.phases();
+        let mut labels: Vec<&'static str> = base_phases.iter().map(|(label, _, _)| *label).collect();
+        for (label, _, _) in &target_phases {
+            if !labels.contains(label) {
+                labels.push(label);
+            }
+        }
+        let phase = |phases: &[(&'static str, f64, f64)], label: &str| {
+            phases
+                .iter()
+                .find(|(name, _, _)| *name == label)
+                .map(|(_, _, duration)| *duration)
+        };
+        let size_diff = target.size_bytes as i64 - base.size_bytes as i64;
+
+        div()
+            .flex_col()
+            .gap_1()
+            .child(row(
+                "Status".into(),
+                status(base),
+                status(target),
+                (base.status != target.status)
+                    .then(|| ("changed".to_string(), status_color(target.status))),
+            ))
+            .child(row(
+                "Size".into(),
+                format_bytes(base.size_bytes as u64),
+                format_bytes(target.size_bytes as u64),
+                (size_diff != 0).then(|| {
+                    let color = if size_diff > 0 { status_color(500) } else { status_color(200) };
+                    (format!("{:+} bytes", size_diff), color)
+                }),
+            ))
+            .child(row(
+                "Total time".into(),
+                format!("{}ms", base.elapsed_ms),
+                format!("{}ms", target.elapsed_ms),
+                ms_delta(base.elapsed_ms as f64, target.elapsed_ms as f64),
+            ))
+            .children(labels.into_iter().map(|label| {
+                let left = phase(&base_phases, label);
+                let right = phase(&target_phases, label);
+                let delta = left.zip(right).and_then(|(left, right)| ms_delta(left, right));
+                row(label.into(), ms(left), ms(right), delta)
+            }))
+    }
+
+    /// A JSON path or header that was added, removed or changed.
+    fn render_change(&self, change: &Change) -> Div {
+        let (sign, color) = match change.kind {
+            ChangeKind::Added => ("+", status_color(200)),
+            ChangeKind::Removed => ("-", status_color(500)),
+            ChangeKind::Changed => ("~", status_color(300)),
+        };
+        div()
+            .flex()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .text_xs()
+            .font_family("monospace")
+            .child(div().flex_none().w_4().text_color(color).child(sign))
+            .child(
+                div()
+                    .w_1_3()
+                    .flex_none()
+                    .text_color(self.theme.syntax_key)
+                    .child(change.path.clone()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .flex()
+                    .flex_wrap()
+                    .gap_2()
+                    .when_some(change.left.clone(), |this, left| {
+                        this.child(div().text_color(status_color(500)).child(left))
+                    })
+                    .when(change.kind == ChangeKind::Changed, |this| {
+                        this.child(div().text_color(self.theme.text_dim).child("→"))
+                    })
+                    .when_some(change.right.clone(), |this, right| {
+                        this.child(div().text_color(status_color(200)).child(right))
+                    }),
+            )
+    }
+
+    fn render_diff_line(&self, line: &DiffLine) -> Div {
+        let (sign, background) = match line.tag {
+            LineTag::Equal => (" ", None),
+            LineTag::Removed => ("-", Some(self.theme.diff_removed)),
+            LineTag::Added => ("+", Some(self.theme.diff_added)),
+        };
+        let number = |number: Option<usize>| {
+            div()
+                .flex_none()
+                .w_12()
+                .text_right()
+                .text_color(self.theme.text_dim)
+                .child(number.map(|n| n.to_string()).unwrap_or_default())
+        };
+        let text: SharedString = if line.text.is_empty() {
+            " ".into()
+        } else {
+            line.text.clone()
+        };
+        div()
+            .flex()
+            .gap_2()
+            .px_2()
+            .whitespace_nowrap()
+            .when_some(background, |this, background| this.bg(background))
+            .child(number(line.left))
+            .child(number(line.right))
+            .child(div().flex_none().w_4().child(sign))
+            .child(div().text_color(self.theme.text).child(text))
+    }
+
+    fn render_response_header(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stream = matches!(response.content, ResponseContent::EventStream(_));
+        let is_error = matches!(response.content, ResponseContent::Error(_));
+        let state = self.state.read(cx);
+        // Streamed responses are shown while the request is still pending
+        let live = state.is_loading();
+        let auto_reconnect = state.sse_auto_reconnect;
+        let snapshot_check = state.snapshot_check.clone();
+
+        div()
+            .p_3()
+            .border_b_1()
+            .border_color(self.theme.border)
+            .flex()
+            .gap_4()
+            .items_center()
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(status_color(response.status))
+                            .child(format!("{}", response.status)),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(self.theme.text_dim)
+                            .child(response.status_text.clone()),
+                    ),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(self.theme.text_dim)
+                    .child(format!("Time: {}ms", response.elapsed_ms)),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(self.theme.text_dim)
+                    .child(format!("Size: {} bytes", response.size_bytes)),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .px_2()
+                    .py_1()
+                    .bg(self.theme.input_bg)
+                    .border_1()
+                    .border_color(self.theme.input_border)
+                    .child(response.content_type()),
+            )
+            .when_some(response.encoding, |this, encoding| {
+                this.child(
+                    div()
+                        .id("response-encoding")
+                        .text_xs()
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(self.theme.input_border)
+                        .cursor_pointer()
+                        .text_color(self.theme.text_dim)
+                        .on_click(cx.listener(|view, _, _, cx| {
+                            view.encoding_menu_open = !view.encoding_menu_open;
+                            cx.notify();
+                        }))
+                        .child(format!("{} ({}) ▾", encoding.name(), encoding.source_label())),
+                )
+            })
+            .when(live, |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(status_color(200))
+                        .child("● Live"),
+                )
+            })
+            .when_some(snapshot_check, |this, check| {
+                let (label, color) = if check.differs {
+                    (format!("Differs from snapshot \"{}\"", check.name), status_color(500))
+                } else {
+                    (format!("Matches snapshot \"{}\"", check.name), status_color(200))
+                };
+                this.child(
+                    div()
+                        .id("snapshot-check")
+                        .text_xs()
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(color)
+                        .text_color(color)
+                        .cursor_pointer()
+                        .on_click(cx.listener(move |view, _, _, cx| {
+                            view.compare_base = Some(check.entry_id);
+                            view.compare_target = None;
+                            view.active_response_tab = ResponseTab::Compare;
+                            cx.notify();
+                        }))
+                        .child(label),
+                )
+            })
+            .child(div().flex_1())
+            .when(is_stream, |this| {
+                this.child(
+                    div()
+                        .id("sse-auto-reconnect")
+                        .text_xs()
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(self.theme.input_border)
+                        .cursor_pointer()
+                        .when(auto_reconnect, |this| {
+                            this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                        })
+                        .on_click(cx.listener(|view, _, _, cx| {
+                            view.state
+                                .update(cx, |state, cx| state.toggle_sse_auto_reconnect(cx));
+                        }))
+                        .child("Auto-reconnect"),
+                )
+            })
+            .when(live, |this| {
+                this.child(
+                    div()
+                        .id("stop-stream")
+                        .text_xs()
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(self.theme.input_border)
+                        .cursor_pointer()
+                        .on_click(cx.listener(|view, _, _, cx| {
+                            view.state.update(cx, |state, cx| state.cancel_request(cx));
+                        }))
+                        .child("Stop"),
+                )
+            })
+            .when(!is_stream && !live, |this| {
+                this.child(
+                    div()
+                        .id("save-response")
+                        .text_xs()
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(self.theme.input_border)
+                        .cursor_pointer()
+                        .on_click(cx.listener(|view, _, _, cx| view.save_response(cx)))
+                        .child("Save to file"),
+                )
+                .when_some(self.file_error.clone(), |this, error| {
+                    this.child(div().text_xs().text_color(status_color(500)).child(error))
+                })
+                .when(!is_error, |this| {
+                    this.child(
+                        div()
+                            .id("toggle-snapshot-bar")
+                            .text_xs()
+                            .px_2()
+                            .py_1()
+                            .border_1()
+                            .border_color(self.theme.input_border)
+                            .cursor_pointer()
+                            .when(self.snapshot_bar_open, |this| {
+                                this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                            })
+                            .on_click(cx.listener(|view, _, window, cx| view.open_snapshot_bar(window, cx)))
+                            .child("Snapshots"),
+                    )
+                })
+            })
+    }
+
+    fn render_response_body(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match &response.content {
+            ResponseContent::Json(..) => self.render_json_response(cx).into_any_element(),
+            ResponseContent::Decoded(format, ..) => div()
+                .size_full()
+                .flex_col()
+                .gap_2()
+                .when(*format == BinaryFormat::Protobuf, |this| {
+                    this.child(self.render_protobuf_message_picker(cx))
+                })
+                .child(self.render_json_response(cx))
+                .into_any_element(),
+            ResponseContent::Text(_) | ResponseContent::Xml(_) => self
+                .render_text_view("text-response", cx)
+                .into_any_element(),
+            ResponseContent::Html(_, blocks) => self
+                .render_html_response(blocks, cx)
+                .into_any_element(),
+            ResponseContent::Image(..) => self
+                .render_image_response(response, cx)
+                .into_any_element(),
+            ResponseContent::Pdf(_) | ResponseContent::Media(..) => self
+                .render_document_response(response, cx)
+                .into_any_element(),
+            ResponseContent::Binary => self
+                .render_binary_response(response, cx)
+                .into_any_element(),
+            ResponseContent::EventStream(events) => {
+                self.render_event_stream(events).into_any_element()
+            }
+            ResponseContent::NdJson(records) => self.render_ndjson(records).into_any_element(),
+            ResponseContent::Error(error) => self.render_error_response(error).into_any_element(),
+        };
+
+        div()
+            .flex_1()
+            .flex_col()
+            .p_4()
+            .gap_2()
+            .when(response.body.is_spooled(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(self.theme.text_dim)
+                        .child(format!(
+                            "Showing the first {} of {}. Use \"Save to file\" to get the full body.",
+                            format_bytes(response.body.preview().len() as u64),
+                            format_bytes(response.size_bytes as u64)
+                        )),
+                )
+            })
+            .when_some(
+                response.encoding.filter(|_| self.encoding_menu_open),
+                |this, encoding| this.child(self.render_encoding_menu(encoding, cx)),
+            )
+            .child(content)
+    }
+
+    fn render_encoding_menu(&self, current: TextEncoding, cx: &mut Context<Self>) -> impl IntoElement {
+        let manual = self.state.read(cx).encoding_override.is_some();
+        let option = |id: ElementId, label: SharedString, active: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .when(active, |this| {
+                    this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                })
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_1()
+            .items_center()
+            .child(div().text_xs().text_color(self.theme.text_dim).child("Decode as"))
+            .child(option("encoding-auto".into(), "Auto-detect".into(), !manual).on_click(
+                cx.listener(|view, _, _, cx| {
+                    view.encoding_menu_open = false;
+                    view.state
+                        .update(cx, |state, cx| state.set_encoding_override(None, cx));
+                }),
+            ))
+            .children(charset::OVERRIDE_ENCODINGS.iter().enumerate().filter_map(|(i, label)| {
+                let encoding = charset::for_label(label)?;
+                let active = manual && current.encoding == encoding;
+                Some(
+                    option(("encoding", i).into(), encoding.name().into(), active).on_click(
+                        cx.listener(move |view, _, _, cx| {
+                            view.encoding_menu_open = false;
+                            view.state.update(cx, |state, cx| {
+                                state.set_encoding_override(Some(encoding), cx)
+                            });
+                        }),
+                    ),
+                )
+            }))
+    }
+
+    fn render_event_stream(&self, events: &[SseEvent]) -> impl IntoElement {
+        div()
+            .id("event-stream")
+            .size_full()
+            .bg(self.theme.input_bg)
+            .border_1()
+            .border_color(self.theme.border)
+            .overflow_y_scroll()
+            .track_scroll(&self.live_scroll)
+            .when(events.is_empty(), |this| {
+                this.child(
+                    div()
+                        .p_3()
+                        .text_sm()
+                        .text_color(self.theme.text_dim)
+                        .child("Connected. Waiting for events..."),
+                )
+            })
+            .children(events.iter().map(|event| {
+                let mut meta = vec![event.received_at.to_string(), event.event.to_string()];
+                if let Some(id) = &event.id {
+                    meta.push(format!("id: {}", id));
+                }
+                if let Some(retry) = event.retry {
+                    meta.push(format!("retry: {}ms", retry));
+                }
+
+                div()
+                    .p_2()
+                    .border_b_1()
+                    .border_color(self.theme.border)
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(meta.join(" · ")),
+                    )
+                    .child(
+                        div()
+                            .font_family("monospace")
+                            .text_sm()
+                            .text_color(self.theme.text)
+                            .child(event.data.clone()),
+                    )
+            }))
+    }
+
+    fn render_ndjson(&self, records: &[NdJsonRecord]) -> impl IntoElement {
+        let invalid = records.iter().filter(|record| !record.valid).count();
+        let mut counter = format!("{} records", records.len());
+        if invalid > 0 {
+            counter.push_str(&format!(" · {} invalid", invalid));
+        }
+
+        div()
+            .size_full()
+            .flex_col()
+            .gap_2()
+            .child(div().text_xs().text_color(self.theme.text_dim).child(counter))
+            .child(
+                div()
+                    .id("ndjson-records")
+                    .flex_1()
+                    .bg(self.theme.input_bg)
+                    .border_1()
+                    .border_color(self.theme.border)
+                    .overflow_y_scroll()
+                    .track_scroll(&self.live_scroll)
+                    .children(records.iter().enumerate().map(|(i, record)| {
+                        div()
+                            .p_2()
+                            .border_b_1()
+                            .border_color(self.theme.border)
+                            .flex()
+                            .gap_3()
+                            .child(
+                                div()
+                                    .w_12()
+                                    .flex_shrink_0()
+                                    .text_xs()
+                                    .text_color(if record.valid {
+                                        self.theme.text_dim
+                                    } else {
+                                        status_color(500).into()
+                                    })
+                                    .child(format!("#{}", i + 1)),
                             )
                             .child(
                                 div()
                                     .flex_1()
-                                    .text_xs()
+                                    .font_family("monospace")
+                                    .text_sm()
                                     .text_color(self.theme.text)
-                                    .child(v.clone()),
+                                    .child(record.text.clone()),
                             )
                     })),
             )
     }
 
-    fn render_response_header(&self, response: &Response) -> impl IntoElement {
+    /// Rebuilds the tree rows when the shown JSON document changes.
+    fn sync_json_tree(&mut self, cx: &mut Context<Self>) {
+        let value = match self.state.read(cx).response.as_deref().map(|r| &r.content) {
+            Some(ResponseContent::Json(_, value) | ResponseContent::Decoded(_, _, value)) => {
+                Some(value.clone())
+            }
+            _ => None,
+        };
+        let unchanged = match (&value, &self.json_source) {
+            (Some(value), Some(source)) => Arc::ptr_eq(value, source),
+            (None, None) => true,
+            _ => false,
+        };
+        if unchanged {
+            return;
+        }
+        self.json_source = value;
+        self.json_expanded = HashSet::from([String::new()]);
+        self.rebuild_json_rows();
+
+        // Restore the filter last used for this request
+        let filter = self.state.read(cx).json_filter();
+        self.filter_input
+            .update(cx, |input, cx| input.set_text(filter.to_string(), cx));
+        self.refresh_json_filter(cx);
+    }
+
+    /// The body text shown as lines, if the current view shows text.
+    fn displayed_text(&self, cx: &App) -> Option<(SharedString, Option<Language>)> {
+        if self.active_response_tab != ResponseTab::Body {
+            return None;
+        }
+        let response = self.state.read(cx).response.clone()?;
+        match &response.content {
+            ResponseContent::Json(text, _) | ResponseContent::Decoded(_, text, _) => {
+                match &self.json_filter_output {
+                    Some(Ok(output)) => Some((output.text.clone(), Some(Language::Json))),
+                    Some(Err(_)) => None,
+                    None if self.json_raw => Some((text.clone(), Some(Language::Json))),
+                    None => None,
+                }
+            }
+            ResponseContent::Text(text) => Some((
+                text.clone(),
+                response
+                    .header("content-type")
+                    .and_then(|ct| Language::from_content_type(ct)),
+            )),
+            ResponseContent::Xml(text) => Some((text.clone(), Some(Language::Markup))),
+            ResponseContent::Html(source, _) if !self.html_preview => {
+                Some((source.clone(), Some(Language::Markup)))
+            }
+            _ => None,
+        }
+    }
+
+    fn sync_text_lines(&mut self, cx: &mut Context<Self>) {
+        let Some((text, language)) = self.displayed_text(cx) else {
+            if self.text_lines.take().is_some() {
+                self.search_version += 1;
+            }
+            return;
+        };
+        if self.text_lines.as_ref().is_some_and(|lines| lines.is_for(&text)) {
+            return;
+        }
+        self.search_version += 1;
+        let lines = TextLines::new(text, language);
+        self.text_list.reset(lines.len());
+        self.text_scroll.scroll_to_item(0, ScrollStrategy::Top);
+        self.text_lines = Some(lines);
+    }
+
+    fn refresh_json_filter(&mut self, cx: &mut Context<Self>) {
+        let filter = self.filter_input.read(cx).text();
+        self.json_filter_output = match &self.json_source {
+            Some(value) if !filter.trim().is_empty() => Some(json_filter::apply(value, &filter)),
+            _ => None,
+        };
+        self.search_version += 1;
+        cx.notify();
+    }
+
+    fn rebuild_json_rows(&mut self) {
+        self.json_rows = match &self.json_source {
+            Some(value) => json_tree::visible_rows(value, &self.json_expanded),
+            None => Vec::new(),
+        };
+        self.search_version += 1;
+    }
+
+    fn toggle_json_node(&mut self, pointer: &str, cx: &mut Context<Self>) {
+        if !self.json_expanded.remove(pointer) {
+            self.json_expanded.insert(pointer.to_string());
+        }
+        self.rebuild_json_rows();
+        cx.notify();
+    }
+
+    fn expand_all_json(&mut self, cx: &mut Context<Self>) {
+        if let Some(value) = &self.json_source {
+            self.json_expanded = json_tree::container_pointers(value);
+            self.rebuild_json_rows();
+            cx.notify();
+        }
+    }
+
+    fn collapse_all_json(&mut self, cx: &mut Context<Self>) {
+        self.json_expanded = HashSet::from([String::new()]);
+        self.rebuild_json_rows();
+        cx.notify();
+    }
+
+    fn copy_json_value(&self, pointer: &str, cx: &mut Context<Self>) {
+        let Some(value) = self.json_source.as_ref().and_then(|v| v.pointer(pointer)) else {
+            return;
+        };
+        let text = match value {
+            Value::String(text) => text.clone(),
+            value => serde_json::to_string_pretty(value).unwrap_or_default(),
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+    }
+
+    fn render_json_row(&self, ix: usize, row: &JsonRow, cx: &mut Context<Self>) -> Stateful<Div> {
+        let value_color = match row.kind {
+            JsonKind::Object | JsonKind::Array => self.theme.text_dim,
+            JsonKind::String => self.theme.syntax_string,
+            JsonKind::Number => self.theme.syntax_number,
+            JsonKind::Bool | JsonKind::Null => self.theme.syntax_keyword,
+        };
+        let action = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .px_1()
+                .text_xs()
+                .text_color(self.theme.text_dim)
+                .cursor_pointer()
+                .child(label)
+        };
+        let pointer = row.pointer.clone();
+        let copy_pointer = row.pointer.clone();
+        let path = row.path.clone();
+
         div()
-            .p_3()
-            .border_b_1()
-            .border_color(self.theme.border)
+            .id(SharedString::from(format!("json-row{}", row.pointer)))
+            .h(px(22.))
+            .w_full()
             .flex()
-            .gap_4()
             .items_center()
+            .gap_1()
+            .pl(px(8. + row.depth as f32 * 16.))
+            .pr_2()
+            .font_family("monospace")
+            .text_sm()
+            .when(row.expandable(), |this| {
+                this.cursor_pointer()
+                    .on_click(cx.listener(move |view, _, _, cx| view.toggle_json_node(&pointer, cx)))
+            })
+            .child(
+                div()
+                    .w_3()
+                    .text_color(self.theme.text_dim)
+                    .child(match (row.expandable(), row.expanded) {
+                        (false, _) => "",
+                        (true, true) => "▾",
+                        (true, false) => "▸",
+                    }),
+            )
+            .children(row.key.clone().map(|key| {
+                let highlights = self.hit_highlights(ix, 0, key.len());
+                div()
+                    .text_color(self.theme.syntax_key)
+                    .child(StyledText::new(format!("{}:", key)).with_highlights(highlights))
+            }))
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .text_color(value_color)
+                    .child(StyledText::new(row.summary.clone()).with_highlights(
+                        // Hits are found in "key: summary"
+                        self.hit_highlights(
+                            ix,
+                            row.key.as_ref().map_or(0, |key| key.len() + 2),
+                            row.summary.len(),
+                        ),
+                    )),
+            )
+            .child(action("copy-value", "Copy value").on_click(cx.listener(
+                move |view, _, _, cx| {
+                    cx.stop_propagation();
+                    view.copy_json_value(&copy_pointer, cx);
+                },
+            )))
+            .child(action("copy-path", "Copy path").on_click(cx.listener(
+                move |_, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.write_to_clipboard(ClipboardItem::new_string(path.to_string()));
+                },
+            )))
+    }
+
+    fn render_json_response(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let toolbar_button = |id: &'static str, label: &'static str, active: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .when(active, |this| {
+                    this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                })
+                .child(label)
+        };
+
+        let toolbar = div()
+            .flex()
+            .gap_2()
+            .child(toolbar_button("json-tree", "Tree", !self.json_raw).on_click(cx.listener(
+                |view, _, _, cx| {
+                    view.json_raw = false;
+                    cx.notify();
+                },
+            )))
+            .child(toolbar_button("json-raw", "Raw", self.json_raw).on_click(cx.listener(
+                |view, _, _, cx| {
+                    view.json_raw = true;
+                    cx.notify();
+                },
+            )))
+            .when(!self.json_raw, |this| {
+                this.child(div().w_4())
+                    .child(toolbar_button("json-expand-all", "Expand all", false).on_click(
+                        cx.listener(|view, _, _, cx| view.expand_all_json(cx)),
+                    ))
+                    .child(toolbar_button("json-collapse-all", "Collapse all", false).on_click(
+                        cx.listener(|view, _, _, cx| view.collapse_all_json(cx)),
+                    ))
+            });
+
+        let content = if let Some(output) = &self.json_filter_output {
+            match output {
+                Ok(output) => div()
+                    .size_full()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(self.theme.text_dim)
+                            .child(format!("{} matches", output.matches)),
+                    )
+                    .child(self.render_text_view("json-response", cx))
+                    .into_any_element(),
+                Err(error) => div()
+                    .text_xs()
+                    .text_color(status_color(500))
+                    .child(format!("Invalid filter: {}", error))
+                    .into_any_element(),
+            }
+        } else if self.json_raw {
+            self.render_text_view("json-response", cx).into_any_element()
+        } else {
+            div()
+                .size_full()
+                .bg(self.theme.input_bg)
+                .border_1()
+                .border_color(self.theme.border)
+                .py_1()
+                .child(
+                    uniform_list(
+                        "json-tree-rows",
+                        self.json_rows.len(),
+                        cx.processor(|view, range: std::ops::Range<usize>, _window, cx| {
+                            range
+                                .map(|ix| {
+                                    let row = view.json_rows[ix].clone();
+                                    view.render_json_row(ix, &row, cx)
+                                })
+                                .collect::<Vec<_>>()
+                        }),
+                    )
+                    .size_full()
+                    .track_scroll(self.json_scroll.clone()),
+                )
+                .into_any_element()
+        };
+
+        div()
+            .size_full()
+            .flex_col()
+            .gap_2()
+            .child(self.filter_input.clone())
+            .child(toolbar)
+            .child(content)
+    }
+
+    /// Renders the displayed body text as a virtualized list of lines, so
+    /// only the lines on screen are laid out.
+    fn render_text_view(&self, id: &'static str, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(lines) = &self.text_lines else {
+            return div().into_any_element();
+        };
+
+        let toolbar = div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .text_xs()
+            .text_color(self.theme.text_dim)
+            .child(format!("{} lines", lines.len()))
+            .child(
+                div()
+                    .id("soft-wrap")
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(self.theme.input_border)
+                    .cursor_pointer()
+                    .when(self.soft_wrap, |this| {
+                        this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                    })
+                    .child("Wrap")
+                    .on_click(cx.listener(|view, _, _, cx| {
+                        view.soft_wrap = !view.soft_wrap;
+                        view.scroll_to_search_hit();
+                        cx.notify();
+                    })),
+            );
+
+        let body = if self.soft_wrap {
+            let view = cx.entity();
+            list(self.text_list.clone(), move |ix, _window, cx| {
+                view.read(cx).render_text_line(ix).into_any_element()
+            })
+            .size_full()
+            .into_any_element()
+        } else {
+            uniform_list(
+                id,
+                lines.len(),
+                cx.processor(|view, range: Range<usize>, _window, _cx| {
+                    range
+                        .map(|ix| view.render_text_line(ix))
+                        .collect::<Vec<_>>()
+                }),
+            )
+            .size_full()
+            .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+            .with_width_from_item(Some(lines.longest))
+            .track_scroll(self.text_scroll.clone())
+            .into_any_element()
+        };
+
+        div()
+            .size_full()
+            .flex_col()
+            .gap_1()
+            .child(toolbar)
+            .child(
+                div()
+                    .size_full()
+                    .bg(self.theme.input_bg)
+                    .border_1()
+                    .border_color(self.theme.border)
+                    .py_1()
+                    .font_family("monospace")
+                    .text_sm()
+                    .text_color(self.theme.text)
+                    .child(body),
+            )
+            .into_any_element()
+    }
+
+    /// One body line with its line number, syntax colors and find hits.
+    fn render_text_line(&self, ix: usize) -> Div {
+        let Some(lines) = &self.text_lines else {
+            return div();
+        };
+        let line = lines.line(ix);
+        let syntax = highlight::highlights(lines.tokens(ix), &self.theme);
+        let hits = if matches!(&self.search_source, Some(SearchSource::Text(text)) if lines.is_for(text)) {
+            self.hit_highlights(ix, 0, line.len())
+        } else {
+            Vec::new()
+        };
+        let text: SharedString = if line.is_empty() {
+            " ".into()
+        } else {
+            line.to_string().into()
+        };
+        let gutter = lines.len().to_string().len() as f32 * 8. + 8.;
+
+        div()
+            .flex()
+            .px_2()
+            .gap_2()
+            .when(!self.soft_wrap, |this| this.whitespace_nowrap())
+            .child(
+                div()
+                    .flex_none()
+                    .w(px(gutter))
+                    .text_right()
+                    .text_color(self.theme.text_dim)
+                    .child((ix + 1).to_string()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .child(StyledText::new(text).with_highlights(combine_highlights(syntax, hits))),
+            )
+    }
+
+    /// Find hits on `line` that fall in `offset..offset + len`, relative to `offset`.
+    fn hit_highlights(&self, line: usize, offset: usize, len: usize) -> Highlights {
+        let first = self.search_hits.partition_point(|hit| hit.line < line);
+        self.search_hits[first..]
+            .iter()
+            .enumerate()
+            .take_while(|(_, hit)| hit.line == line)
+            .filter_map(|(i, hit)| {
+                let start = hit.range.start.max(offset);
+                let end = hit.range.end.min(offset + len);
+                let background = if first + i == self.search_current {
+                    self.theme.search_match_current
+                } else {
+                    self.theme.search_match
+                };
+                (start < end).then(|| {
+                    let style = HighlightStyle {
+                        background_color: Some(background),
+                        ..Default::default()
+                    };
+                    (start - offset..end - offset, style)
+                })
+            })
+            .collect()
+    }
+
+    fn current_search_source(&self, cx: &App) -> Option<SearchSource> {
+        let response = self.state.read(cx).response.clone()?;
+        match self.active_response_tab {
+            ResponseTab::Headers => Some(SearchSource::Headers(
+                self.visible_headers(&response, cx)
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value).into())
+                    .collect(),
+            )),
+            ResponseTab::Body => match &response.content {
+                ResponseContent::Json(..) | ResponseContent::Decoded(..)
+                    if self.json_filter_output.is_none() && !self.json_raw =>
+                {
+                    Some(SearchSource::JsonRows(
+                        self.json_rows
+                            .iter()
+                            .map(|row| match &row.key {
+                                Some(key) => format!("{}: {}", key, row.summary).into(),
+                                None => row.summary.clone(),
+                            })
+                            .collect(),
+                    ))
+                }
+                _ => self
+                    .displayed_text(cx)
+                    .map(|(text, _)| SearchSource::Text(text)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Rebuilds the source and re-runs the search when the searched view changed.
+    fn sync_search(&mut self, cx: &mut Context<Self>) {
+        if !self.search_open {
+            return;
+        }
+        let response = self
+            .state
+            .read(cx)
+            .response
+            .as_ref()
+            .map(Arc::downgrade)
+            .unwrap_or_default();
+        if !Weak::ptr_eq(&response, &self.search_response) || self.search_tab != self.active_response_tab {
+            self.search_response = response;
+            self.search_tab = self.active_response_tab;
+            self.search_version += 1;
+        }
+        if self.search_synced == Some(self.search_version) {
+            return;
+        }
+        self.search_synced = Some(self.search_version);
+        self.search_source = self.current_search_source(cx);
+        self.run_search(cx);
+    }
+
+    fn run_search(&mut self, cx: &App) {
+        let query = self.search_input.read(cx).text();
+        self.search_hits.clear();
+        self.search_error = None;
+        if !query.is_empty()
+            && let Some(source) = &self.search_source
+        {
+            match Matcher::new(&query, self.search_options) {
+                Ok(matcher) => {
+                    self.search_hits = match source {
+                        SearchSource::Text(text) => match &self.text_lines {
+                            Some(lines) if lines.is_for(text) => search::text_hits(&matcher, lines),
+                            _ => Vec::new(),
+                        },
+                        SearchSource::JsonRows(rows) | SearchSource::Headers(rows) => {
+                            search::row_hits(&matcher, rows)
+                        }
+                    }
+                }
+                Err(e) => self.search_error = Some(e.into()),
+            }
+        }
+        self.search_current = self
+            .search_current
+            .min(self.search_hits.len().saturating_sub(1));
+    }
+
+    fn scroll_to_search_hit(&self) {
+        let Some(hit) = self.search_hits.get(self.search_current) else {
+            return;
+        };
+        match self.search_source {
+            Some(SearchSource::JsonRows(_)) => {
+                self.json_scroll.scroll_to_item(hit.line, ScrollStrategy::Center)
+            }
+            Some(SearchSource::Text(_)) if self.soft_wrap => {
+                self.text_list.scroll_to_reveal_item(hit.line)
+            }
+            Some(SearchSource::Text(_)) => {
+                self.text_scroll.scroll_to_item(hit.line, ScrollStrategy::Center)
+            }
+            Some(SearchSource::Headers(_)) => self.search_scroll.scroll_to_item(hit.line),
+            None => {}
+        }
+    }
+
+    fn next_search_hit(&mut self, cx: &mut Context<Self>) {
+        if !self.search_hits.is_empty() {
+            self.search_current = (self.search_current + 1) % self.search_hits.len();
+            self.scroll_to_search_hit();
+            cx.notify();
+        }
+    }
+
+    fn previous_search_hit(&mut self, cx: &mut Context<Self>) {
+        if !self.search_hits.is_empty() {
+            let len = self.search_hits.len();
+            self.search_current = (self.search_current + len - 1) % len;
+            self.scroll_to_search_hit();
+            cx.notify();
+        }
+    }
+
+    fn toggle_search_option(&mut self, regex: bool, cx: &mut Context<Self>) {
+        if regex {
+            self.search_options.regex = !self.search_options.regex;
+        } else {
+            self.search_options.case_sensitive = !self.search_options.case_sensitive;
+        }
+        self.search_current = 0;
+        self.run_search(cx);
+        self.scroll_to_search_hit();
+        cx.notify();
+    }
+
+    fn open_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_open = true;
+        if !matches!(self.active_response_tab, ResponseTab::Headers) {
+            self.active_response_tab = ResponseTab::Body;
+        }
+        self.search_synced = None;
+        self.sync_search(cx);
+        self.search_input.read(cx).focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
+    fn close_search(&mut self, cx: &mut Context<Self>) {
+        self.search_open = false;
+        self.search_source = None;
+        self.search_synced = None;
+        self.search_hits.clear();
+        self.search_error = None;
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "f" if keystroke.modifiers.secondary() => {
+                cx.stop_propagation();
+                self.open_search(window, cx);
+            }
+            "escape" if self.search_open => self.close_search(cx),
+            _ => {}
+        }
+    }
+
+    fn render_search_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let button = |id: &'static str, label: &'static str, active: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .when(active, |this| {
+                    this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                })
+                .child(label)
+        };
+        let query_empty = self.search_input.read(cx).text().is_empty();
+        let (status, status_color_value) = match &self.search_error {
+            Some(error) => (format!("Invalid pattern: {}", error), status_color(500).into()),
+            None if query_empty => (String::new(), self.theme.text_dim),
+            None if self.search_source.is_none() => {
+                ("This view cannot be searched".to_string(), self.theme.text_dim)
+            }
+            None if self.search_hits.is_empty() => ("No results".to_string(), self.theme.text_dim),
+            None => {
+                let mut status = format!("{} of {}", self.search_current + 1, self.search_hits.len());
+                if self.search_hits.len() >= search::MAX_MATCHES {
+                    status.push('+');
+                }
+                (status, self.theme.text_dim)
+            }
+        };
+
+        div()
+            .px_4()
+            .py_2()
+            .flex()
+            .gap_2()
+            .items_center()
+            .border_b_1()
+            .border_color(self.theme.border)
+            .child(div().w_64().child(self.search_input.clone()))
+            .child(
+                button("search-case", "Aa", self.search_options.case_sensitive)
+                    .on_click(cx.listener(|view, _, _, cx| view.toggle_search_option(false, cx))),
+            )
+            .child(
+                button("search-regex", ".*", self.search_options.regex)
+                    .on_click(cx.listener(|view, _, _, cx| view.toggle_search_option(true, cx))),
+            )
+            .child(div().text_xs().text_color(status_color_value).child(status))
+            .child(div().flex_1())
+            .child(
+                button("search-previous", "↑", false)
+                    .on_click(cx.listener(|view, _, _, cx| view.previous_search_hit(cx))),
+            )
+            .child(
+                button("search-next", "↓", false)
+                    .on_click(cx.listener(|view, _, _, cx| view.next_search_hit(cx))),
+            )
+            .child(
+                button("search-close", "✕", false)
+                    .on_click(cx.listener(|view, _, _, cx| view.close_search(cx))),
+            )
+    }
+
+    /// Chooses the message type a protobuf body is decoded as.
+    fn render_protobuf_message_picker(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let selected = state.protobuf_message.clone();
+        let names = state
+            .grpc_schema
+            .as_ref()
+            .map(|schema| schema.message_names())
+            .unwrap_or_default();
+        let chip = |id: ElementId, label: SharedString, active: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .when(active, |this| {
+                    this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                })
+                .child(label)
+        };
+
+        div()
+            .flex_col()
+            .gap_1()
             .child(
                 div()
                     .flex()
                     .gap_2()
                     .items_center()
+                    .child(div().text_xs().text_color(self.theme.text_dim).child("Decode as"))
                     .child(
-                        div()
-                            .text_sm()
-                            .font_weight(FontWeight::BOLD)
-                            .text_color(if response.status >= 200 && response.status < 300 {
-                                rgb(0x10b981) // green
-                            } else if response.status >= 400 {
-                                rgb(0xef4444) // red
-                            } else {
-                                rgb(0xf59e0b) // yellow
-                            })
-                            .child(format!("{}", response.status)),
+                        chip("protobuf-raw".into(), "Raw wire format".into(), selected.is_none())
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.state
+                                    .update(cx, |state, cx| state.select_protobuf_message(None, cx));
+                            })),
                     )
-                    .child(
-                        div()
-                            .text_sm()
-                            .text_color(self.theme.text_dim)
-                            .child(response.status_text.clone()),
-                    ),
+                    .when(names.is_empty(), |this| {
+                        this.child(chip("protobuf-load".into(), "Load .proto files".into(), false).on_click(
+                            cx.listener(|view, _, _, cx| view.pick_proto_files(cx)),
+                        ))
+                    }),
             )
-            .child(
+            .when(!names.is_empty(), |this| {
+                this.child(
+                    div()
+                        .id("protobuf-messages")
+                        .max_h_24()
+                        .overflow_y_scroll()
+                        .flex()
+                        .flex_wrap()
+                        .gap_1()
+                        .children(names.into_iter().enumerate().map(|(i, name)| {
+                            let active = selected.as_ref() == Some(&name);
+                            chip(("protobuf-message", i).into(), name.clone(), active).on_click(
+                                cx.listener(move |view, _, _, cx| {
+                                    let name = name.clone();
+                                    view.state.update(cx, |state, cx| {
+                                        state.select_protobuf_message(Some(name), cx)
+                                    });
+                                }),
+                            )
+                        })),
+                )
+            })
+            .children(state.protobuf_error.clone().map(|error| {
                 div()
                     .text_xs()
-                    .text_color(self.theme.text_dim)
-                    .child(format!("Time: {}ms", response.elapsed_ms)),
-            )
+                    .text_color(status_color(500))
+                    .child(format!("Showing the raw wire format: {}", error))
+            }))
+    }
+
+    fn render_html_response(
+        &self,
+        blocks: &[PreviewBlock],
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let toolbar_button = |id: &'static str, label: &'static str, active: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .when(active, |this| {
+                    this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                })
+                .child(label)
+        };
+
+        let toolbar = div()
+            .flex()
+            .gap_2()
             .child(
-                div()
-                    .text_xs()
-                    .text_color(self.theme.text_dim)
-                    .child(format!("Size: {} bytes", response.size_bytes)),
+                toolbar_button("html-source", "Source", !self.html_preview).on_click(
+                    cx.listener(|view, _, _, cx| {
+                        view.html_preview = false;
+                        cx.notify();
+                    }),
+                ),
             )
             .child(
-                div()
-                    .text_xs()
-                    .px_2()
-                    .py_1()
-                    .bg(self.theme.input_bg)
-                    .border_1()
-                    .border_color(self.theme.input_border)
-                    .child(response.content_type()),
-            )
-    }
+                toolbar_button("html-preview", "Preview", self.html_preview).on_click(
+                    cx.listener(|view, _, _, cx| {
+                        view.html_preview = true;
+                        cx.notify();
+                    }),
+                ),
+            );
 
-    fn render_response_body(&self, response: &Response) -> impl IntoElement {
-        let content = match &response.content {
-            ResponseContent::Json(json) => self.render_json_response(json).into_any_element(),
-            ResponseContent::Text(text) => self.render_text_response(text).into_any_element(),
-            ResponseContent::Image(bytes, mime_type) => self
-                .render_image_response(bytes, mime_type)
-                .into_any_element(),
-            ResponseContent::Binary(bytes) => self.render_binary_response(bytes).into_any_element(),
-            ResponseContent::Error(error) => self.render_error_response(error).into_any_element(),
+        let content = if self.html_preview {
+            self.render_html_preview(blocks).into_any_element()
+        } else {
+            self.render_text_view("html-source", cx).into_any_element()
         };
 
-        div().flex_1().p_4().child(content)
+        div()
+            .size_full()
+            .flex_col()
+            .gap_2()
+            .child(toolbar)
+            .child(content)
     }
 
-    fn render_json_response(&self, json: &SharedString) -> impl IntoElement {
+    fn render_html_preview(&self, blocks: &[PreviewBlock]) -> impl IntoElement {
         div()
-            .id("json-response")
+            .id("html-preview")
             .size_full()
             .bg(self.theme.input_bg)
             .border_1()
             .border_color(self.theme.border)
             .p_3()
-            .font_family("monospace")
-            .text_sm()
+            .flex_col()
+            .gap_2()
             .text_color(self.theme.text)
             .overflow_y_scroll()
-            .child(json.clone())
+            .when(blocks.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(self.theme.text_dim)
+                        .child("No visible content"),
+                )
+            })
+            .children(blocks.iter().map(|block| {
+                let text = block.text.clone();
+                match block.kind {
+                    BlockKind::Title => div()
+                        .pb_1()
+                        .border_b_1()
+                        .border_color(self.theme.border)
+                        .text_xl()
+                        .font_weight(FontWeight::BOLD)
+                        .child(text),
+                    BlockKind::Heading(level) => div()
+                        .font_weight(FontWeight::BOLD)
+                        .map(|this| match level {
+                            1 => this.text_xl(),
+                            2 => this.text_lg(),
+                            _ => this.text_base(),
+                        })
+                        .child(text),
+                    BlockKind::Paragraph => div().text_sm().child(text),
+                    BlockKind::ListItem(depth) => div()
+                        .pl(px(16. * (depth + 1) as f32))
+                        .text_sm()
+                        .child(format!("• {}", text)),
+                    BlockKind::Code => div()
+                        .p_2()
+                        .bg(self.theme.bg)
+                        .font_family("monospace")
+                        .text_xs()
+                        .child(text),
+                    BlockKind::Rule => div().h(px(1.)).bg(self.theme.border),
+                }
+            }))
+    }
+
+    /// Decodes an image body once per response.
+    fn sync_image(&mut self, cx: &mut Context<Self>) {
+        let Some(Response {
+            content: ResponseContent::Image(bytes, mime_type),
+            ..
+        }) = self.state.read(cx).response.as_deref()
+        else {
+            self.image_bytes = None;
+            self.image = None;
+            return;
+        };
+        if self
+            .image_bytes
+            .as_ref()
+            .is_some_and(|cached| Arc::ptr_eq(cached, bytes))
+        {
+            return;
+        }
+        self.image = Some(image_info::decode(bytes, mime_type).map_err(SharedString::from));
+        self.image_bytes = Some(bytes.clone());
+        self.image_zoom = None;
+        self.image_offset = Point::default();
+    }
+
+    fn zoom_image(&mut self, factor: f32, cx: &mut Context<Self>) {
+        let zoom = self.image_zoom.unwrap_or(1.0) * factor;
+        self.image_zoom = Some(zoom.clamp(MIN_IMAGE_ZOOM, MAX_IMAGE_ZOOM));
+        cx.notify();
     }
 
-    fn render_text_response(&self, text: &SharedString) -> impl IntoElement {
+    fn render_image_response(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let decoded = match &self.image {
+            Some(Ok(decoded)) => decoded,
+            Some(Err(error)) => {
+                return div()
+                    .size_full()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(status_color(500))
+                            .child(format!("Could not decode the image: {}", error)),
+                    )
+                    .child(self.render_binary_response(response, cx))
+                    .into_any_element();
+            }
+            None => return div().into_any_element(),
+        };
+        let info = &decoded.info;
+        let button = |id: &'static str, label: &'static str, active: bool| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .when(active, |this| {
+                    this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                })
+                .child(label)
+        };
+
+        let toolbar = div()
+            .flex()
+            .gap_2()
+            .items_center()
+            .child(button("image-fit", "Fit", self.image_zoom.is_none()).on_click(
+                cx.listener(|view, _, _, cx| {
+                    view.image_zoom = None;
+                    view.image_offset = Point::default();
+                    cx.notify();
+                }),
+            ))
+            .child(button("image-actual-size", "100%", self.image_zoom == Some(1.0)).on_click(
+                cx.listener(|view, _, _, cx| {
+                    view.image_zoom = Some(1.0);
+                    view.image_offset = Point::default();
+                    cx.notify();
+                }),
+            ))
+            .child(
+                button("image-zoom-out", "-", false)
+                    .on_click(cx.listener(|view, _, _, cx| view.zoom_image(1.0 / IMAGE_ZOOM_STEP, cx))),
+            )
+            .child(
+                button("image-zoom-in", "+", false)
+                    .on_click(cx.listener(|view, _, _, cx| view.zoom_image(IMAGE_ZOOM_STEP, cx))),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(self.theme.text_dim)
+                    .child(match self.image_zoom {
+                        Some(zoom) => format!("{:.0}%", zoom * 100.0),
+                        None => "Fit".to_string(),
+                    }),
+            )
+            .child(div().flex_1())
+            .child(div().text_xs().text_color(self.theme.text_dim).child(format!(
+                "{} · {} × {} · {} · {}",
+                info.format,
+                info.width,
+                info.height,
+                info.color,
+                format_bytes(response.size_bytes as u64)
+            )));
+
+        let image = decoded.image.clone();
+        let canvas = div()
+            .id("image-canvas")
+            .flex_1()
+            .relative()
+            .overflow_hidden()
+            .map(|this| match self.image_zoom {
+                None => this.child(img(image).size_full().object_fit(ObjectFit::Contain)),
+                Some(zoom) => this.child(
+                    img(image)
+                        .absolute()
+                        .left(self.image_offset.x)
+                        .top(self.image_offset.y)
+                        .w(px(info.width as f32 * zoom))
+                        .h(px(info.height as f32 * zoom)),
+                ),
+            })
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|view, event: &MouseDownEvent, _, _| {
+                    view.image_drag = Some(event.position);
+                }),
+            )
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|view, _: &MouseUpEvent, _, _| view.image_drag = None),
+            )
+            // Drag pans a zoomed image
+            .on_mouse_move(cx.listener(|view, event: &MouseMoveEvent, _, cx| {
+                let Some(last) = view.image_drag else {
+                    return;
+                };
+                if event.pressed_button != Some(MouseButton::Left) {
+                    view.image_drag = None;
+                } else if view.image_zoom.is_some() {
+                    view.image_offset += event.position - last;
+                    view.image_drag = Some(event.position);
+                    cx.notify();
+                }
+            }))
+            // Ctrl/Cmd + wheel zooms, the wheel alone pans
+            .on_scroll_wheel(cx.listener(|view, event: &ScrollWheelEvent, window, cx| {
+                let delta = event.delta.pixel_delta(window.line_height());
+                if event.modifiers.secondary() {
+                    if delta.y > px(0.) {
+                        view.zoom_image(IMAGE_ZOOM_STEP, cx);
+                    } else if delta.y < px(0.) {
+                        view.zoom_image(1.0 / IMAGE_ZOOM_STEP, cx);
+                    }
+                } else if view.image_zoom.is_some() {
+                    view.image_offset += delta;
+                    cx.notify();
+                }
+            }));
+
         div()
-            .id("text-response")
             .size_full()
             .bg(self.theme.input_bg)
             .border_1()
             .border_color(self.theme.border)
             .p_3()
+            .flex_col()
+            .gap_2()
+            .child(toolbar)
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .gap_3()
+                    .child(canvas)
+                    .when(!info.exif.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .id("image-exif")
+                                .w_64()
+                                .flex_none()
+                                .flex_col()
+                                .gap_1()
+                                .overflow_y_scroll()
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .font_weight(FontWeight::BOLD)
+                                        .text_color(self.theme.text_dim)
+                                        .child("EXIF"),
+                                )
+                                .children(info.exif.iter().map(|(tag, value)| {
+                                    div()
+                                        .flex_col()
+                                        .text_xs()
+                                        .child(div().text_color(self.theme.text_dim).child(tag.clone()))
+                                        .child(div().text_color(self.theme.text).child(value.clone()))
+                                })),
+                        )
+                    }),
+            )
+            .into_any_element()
+    }
+
+    fn response_bytes(&self, cx: &App) -> Arc<[u8]> {
+        self.state
+            .read(cx)
+            .response
+            .as_ref()
+            .map(|response| response.body.preview().clone())
+            .unwrap_or_else(|| Arc::from([]))
+    }
+
+    fn jump_to_offset(&mut self, cx: &mut Context<Self>) {
+        let text = self.offset_input.read(cx).text();
+        let len = self.response_bytes(cx).len();
+        match hex::parse_offset(&text) {
+            Some(offset) if offset < len => {
+                self.offset_error = None;
+                self.hex_anchor = offset;
+                self.hex_selection = Some(offset..offset + 1);
+                self.hex_scroll
+                    .scroll_to_item(offset / BYTES_PER_LINE, ScrollStrategy::Center);
+            }
+            Some(_) => self.offset_error = Some(format!("Offset is past the end ({} bytes)", len).into()),
+            None => self.offset_error = Some("Enter a decimal or 0x-prefixed hex offset".into()),
+        }
+        cx.notify();
+    }
+
+    /// Selects a byte; shift-click extends the selection from the last anchor.
+    fn select_byte(&mut self, offset: usize, extend: bool, cx: &mut Context<Self>) {
+        if extend && self.hex_selection.is_some() {
+            let start = self.hex_anchor.min(offset);
+            let end = self.hex_anchor.max(offset) + 1;
+            self.hex_selection = Some(start..end);
+        } else {
+            self.hex_anchor = offset;
+            self.hex_selection = Some(offset..offset + 1);
+        }
+        cx.notify();
+    }
+
+    fn copy_hex_selection(&mut self, cx: &mut Context<Self>) {
+        let bytes = self.response_bytes(cx);
+        if let Some(range) = self.hex_selection.clone()
+            && let Some(selected) = bytes.get(range)
+        {
+            cx.write_to_clipboard(ClipboardItem::new_string(hex::to_hex(selected).to_string()));
+        }
+    }
+
+    fn render_hex_row(&self, line: usize, bytes: &[u8], cx: &mut Context<Self>) -> Stateful<Div> {
+        let start = line * BYTES_PER_LINE;
+        let chunk = &bytes[start..(start + BYTES_PER_LINE).min(bytes.len())];
+        let selected = |offset: usize| {
+            self.hex_selection
+                .as_ref()
+                .is_some_and(|range| range.contains(&offset))
+        };
+        let cell = |offset: usize, text: String, width: Pixels| {
+            div()
+                .id(("hex-byte", offset))
+                .w(width)
+                .flex_shrink_0()
+                .cursor_pointer()
+                .when(selected(offset), |this| {
+                    this.bg(self.theme.accent).text_color(self.theme.accent_text)
+                })
+                .child(text)
+        };
+
+        div()
+            .id(("hex-row", line))
+            .px_3()
+            .flex()
+            .gap_4()
             .font_family("monospace")
             .text_sm()
-            .text_color(self.theme.text)
-            .overflow_y_scroll()
-            .child(text.clone())
+            .child(
+                div()
+                    .w_20()
+                    .flex_shrink_0()
+                    .text_color(self.theme.text_dim)
+                    .child(format!("{:08x}", start)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_1()
+                    .text_color(self.theme.text)
+                    .children(chunk.iter().enumerate().map(|(i, byte)| {
+                        let offset = start + i;
+                        cell(offset, format!("{:02x}", byte), px(20.))
+                            .when(i == BYTES_PER_LINE / 2, |this| this.ml_2())
+                            .on_click(cx.listener(move |view, event: &ClickEvent, _, cx| {
+                                view.select_byte(offset, event.modifiers().shift, cx)
+                            }))
+                    })),
+            )
+            .child(
+                div()
+                    .flex()
+                    .text_color(self.theme.syntax_string)
+                    .children(chunk.iter().enumerate().map(|(i, &byte)| {
+                        let offset = start + i;
+                        cell(offset, hex::ascii(byte).to_string(), px(8.))
+                            .on_click(cx.listener(move |view, event: &ClickEvent, _, cx| {
+                                view.select_byte(offset, event.modifiers().shift, cx)
+                            }))
+                    })),
+            )
     }
 
-    fn render_image_response(&self, bytes: &[u8], mime_type: &SharedString) -> impl IntoElement {
-        let format = ImageFormat::from_mime_type(mime_type);
+    fn render_binary_response(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let bytes = response.body.preview();
+        let mut summary = format!("Binary data: {} bytes", response.size_bytes);
+        if let Some(format) = hex::sniff(bytes) {
+            summary.push_str(&format!(" · {}", format));
+        }
+        let selection = self.hex_selection.clone().filter(|range| range.end <= bytes.len());
+
+        let toolbar_button = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .child(label)
+        };
 
         div()
             .size_full()
-            .bg(self.theme.input_bg)
-            .border_1()
-            .border_color(self.theme.border)
-            .p_3()
             .flex_col()
             .gap_2()
             .child(
                 div()
-                    .text_sm()
-                    .text_color(self.theme.text_dim)
-                    .child(format!("Image ({}): {} bytes", mime_type, bytes.len())),
+                    .flex()
+                    .gap_3()
+                    .items_center()
+                    .child(div().text_sm().text_color(self.theme.text).child(summary))
+                    .child(div().flex_1())
+                    .when_some(self.file_error.clone(), |this, error| {
+                        this.child(div().text_xs().text_color(status_color(500)).child(error))
+                    })
+                    .child(
+                        toolbar_button("hex-save", "Save to file")
+                            .on_click(cx.listener(|view, _, _, cx| view.save_response(cx))),
+                    ),
             )
-            .child({
-                if let Some(format) = format {
-                    let image = Image::from_bytes(format, bytes.to_vec());
-                    div()
-                        .flex_1()
-                        .child(
-                            img(Arc::new(image))
-                                .size_full()
-                                .object_fit(ObjectFit::Contain),
+            .child(
+                div()
+                    .flex()
+                    .gap_3()
+                    .items_center()
+                    .child(div().w_64().child(self.offset_input.clone()))
+                    .child(
+                        toolbar_button("hex-jump", "Go")
+                            .on_click(cx.listener(|view, _, _, cx| view.jump_to_offset(cx))),
+                    )
+                    .when_some(self.offset_error.clone(), |this, error| {
+                        this.child(div().text_xs().text_color(status_color(500)).child(error))
+                    })
+                    .when_some(selection, |this, range| {
+                        this.child(div().flex_1())
+                            .child(div().text_xs().text_color(self.theme.text_dim).child(format!(
+                                "Selected 0x{:x}–0x{:x} ({} bytes)",
+                                range.start,
+                                range.end - 1,
+                                range.len()
+                            )))
+                            .child(
+                                toolbar_button("hex-copy", "Copy hex").on_click(
+                                    cx.listener(|view, _, _, cx| view.copy_hex_selection(cx)),
+                                ),
+                            )
+                    }),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .bg(self.theme.input_bg)
+                    .border_1()
+                    .border_color(self.theme.border)
+                    .py_1()
+                    .child(
+                        uniform_list(
+                            "hex-rows",
+                            hex::line_count(bytes.len()),
+                            cx.processor(|view, range: Range<usize>, _window, cx| {
+                                let bytes = view.response_bytes(cx);
+                                range
+                                    .map(|line| view.render_hex_row(line, &bytes, cx))
+                                    .collect::<Vec<_>>()
+                            }),
                         )
-                        .into_any_element()
-                } else {
-                    div()
-                        .text_xs()
-                        .text_color(self.theme.text_dim)
-                        .child(format!("Unsupported image format: {}", mime_type))
-                        .into_any_element()
-                }
-            })
+                        .size_full()
+                        .track_scroll(self.hex_scroll.clone()),
+                    ),
+            )
+    }
+
+    /// Writes the body to a temporary file and opens it with the default app
+    /// for its type.
+    fn open_with_system_app(&mut self, cx: &mut Context<Self>) {
+        let Some(response) = self.state.read(cx).response.clone() else {
+            return;
+        };
+        let extension = match &response.content {
+            ResponseContent::Pdf(_) => "pdf",
+            ResponseContent::Media(mime_type, _) => media::extension(mime_type),
+            _ => "bin",
+        };
+        let suffix = format!(".{}", extension);
+        self.file_error = None;
+
+        let write = cx.background_spawn(async move {
+            let path = tempfile::Builder::new()
+                .prefix("resty-")
+                .suffix(&suffix)
+                .tempfile()?
+                .into_temp_path();
+            response.body.save_to(&path).map(|_| path)
+        });
+        cx.spawn(move |view: WeakEntity<Workspace>, cx: &mut AsyncApp| {
+            let cx = cx.clone();
+            async move {
+                let result = write.await;
+                let _ = cx.update(|cx| {
+                    view.update(cx, |view, cx| {
+                        match result {
+                            Ok(path) => {
+                                cx.open_with_system(&path);
+                                view.opened_files.push(path);
+                            }
+                            Err(e) => {
+                                view.file_error = Some(
+                                    format!("Failed to write the response for opening: {}", e).into(),
+                                );
+                            }
+                        }
+                        cx.notify();
+                    })
+                });
+            }
+        })
+        .detach();
     }
 
-    fn render_binary_response(&self, bytes: &[u8]) -> impl IntoElement {
+    /// PDF and audio/video bodies: what their headers tell, and ways to open them.
+    fn render_document_response(&self, response: &Response, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut rows: Vec<(SharedString, SharedString)> = Vec::new();
+        let note = match &response.content {
+            ResponseContent::Pdf(info) => {
+                if let Some(version) = &info.version {
+                    rows.push(("PDF version".into(), version.clone()));
+                }
+                rows.push((
+                    "Pages".into(),
+                    info.pages
+                        .map_or("unknown".into(), |pages| pages.to_string().into()),
+                ));
+                if info.encrypted {
+                    rows.push(("Encrypted".into(), "yes".into()));
+                }
+                rows.extend(info.metadata.iter().cloned());
+                "No preview is rendered for PDF documents; open them with the system viewer."
+            }
+            ResponseContent::Media(mime_type, info) => {
+                rows.push(("Type".into(), mime_type.clone()));
+                rows.push(("Container".into(), info.container.into()));
+                if let Some(duration) = info.duration {
+                    rows.push(("Duration".into(), media::format_clock(duration).into()));
+                }
+                rows.extend(info.fields.iter().cloned());
+                "Audio and video are not played here; open them with the system player."
+            }
+            _ => "",
+        };
+        rows.push(("Size".into(), format_bytes(response.size_bytes as u64).into()));
+
+        let button = |id: &'static str, label: &'static str| {
+            div()
+                .id(id)
+                .px_2()
+                .py_1()
+                .text_xs()
+                .border_1()
+                .border_color(self.theme.input_border)
+                .cursor_pointer()
+                .child(label)
+        };
+
         div()
+            .id("document-response")
             .size_full()
             .bg(self.theme.input_bg)
             .border_1()
             .border_color(self.theme.border)
             .p_3()
             .flex_col()
-            .gap_2()
+            .gap_3()
+            .overflow_y_scroll()
             .child(
                 div()
-                    .text_sm()
-                    .text_color(self.theme.text)
-                    .child(format!("Binary data: {} bytes", bytes.len())),
+                    .flex()
+                    .gap_2()
+                    .child(
+                        button("open-with-system", "Open with system app")
+                            .on_click(cx.listener(|view, _, _, cx| view.open_with_system_app(cx))),
+                    )
+                    .child(
+                        button("save-document", "Save as…")
+                            .on_click(cx.listener(|view, _, _, cx| view.save_response(cx))),
+                    )
+                    .when_some(self.file_error.clone(), |this, error| {
+                        this.child(div().text_xs().text_color(status_color(500)).child(error))
+                    }),
             )
             .child(
                 div()
-                    .text_xs()
-                    .text_color(self.theme.text_dim)
-                    .child("Binary content cannot be displayed as text."),
+                    .flex_col()
+                    .gap_1()
+                    .children(rows.into_iter().map(|(label, value)| {
+                        div()
+                            .flex()
+                            .gap_4()
+                            .text_xs()
+                            .child(div().w_40().flex_none().text_color(self.theme.text_dim).child(label))
+                            .child(div().flex_1().min_w_0().text_color(self.theme.text).child(value))
+                    })),
             )
+            .child(div().text_xs().text_color(self.theme.text_dim).child(note))
     }
 
     fn render_error_response(&self, error: &SharedString) -> impl IntoElement {
@@ -616,13 +4110,76 @@ impl Workspace {
     }
 }
 
+/// Plain text shown when hovering an element.
+struct TextTooltip(SharedString);
+
+impl Render for TextTooltip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::dark();
+        div()
+            .max_w_80()
+            .px_2()
+            .py_1()
+            .bg(theme.sidebar_bg)
+            .border_1()
+            .border_color(theme.border)
+            .text_xs()
+            .text_color(theme.text)
+            .child(self.0.clone())
+    }
+}
+
+fn status_color(status: u16) -> Rgba {
+    if (200..300).contains(&status) {
+        rgb(0x10b981) // green
+    } else if status >= 400 {
+        rgb(0xef4444) // red
+    } else {
+        rgb(0xf59e0b) // yellow
+    }
+}
+
 impl Render for Workspace {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.sync_json_tree(cx);
+        self.sync_text_lines(cx);
+        self.sync_comparison(cx);
+        self.sync_image(cx);
+        self.sync_search(cx);
+
+        // Keep live logs scrolled to the newest entry as entries arrive
+        let state = self.state.read(cx);
+        let (entry_count, follow_bottom) =
+            match (&state.websocket, state.response.as_deref().map(|r| &r.content)) {
+                _ if state.is_grpc() => (
+                    state.grpc_call.as_ref().map_or(0, |call| call.messages.len()),
+                    false,
+                ),
+                (Some(session), _) if state.is_websocket() => (session.log.len(), false),
+                (_, Some(ResponseContent::EventStream(events))) => (events.len(), false),
+                (_, Some(ResponseContent::NdJson(records))) => (records.len(), false),
+                // A chunked text preview follows its bytes rather than entries
+                (_, None) if state.partial_body.is_some() => (
+                    state.download.as_ref().map_or(0, |d| d.received as usize),
+                    true,
+                ),
+                _ => (0, false),
+            };
+        if entry_count > self.followed_entries {
+            if follow_bottom {
+                self.live_scroll.scroll_to_bottom();
+            } else {
+                self.live_scroll.scroll_to_item(entry_count - 1);
+            }
+        }
+        self.followed_entries = entry_count;
+
         div()
             .size_full()
             .flex()
             .bg(self.theme.bg)
             .text_color(self.theme.text)
+            .on_key_down(cx.listener(Self::on_key_down))
             .child(self.render_sidebar(cx))
             .child(
                 div()